@@ -0,0 +1,252 @@
+use std::collections::BTreeSet;
+
+use rust_extensions::date_time::DateTimeAsMicroseconds;
+use trading_sdk_core::{EngineCacheQueryBuilder, PositionsCache};
+
+use crate::{MtPosition, MtPositionCloseReason, MtPositionClosedState};
+
+#[derive(Debug, Clone, Default)]
+pub struct MtClosedPositionsFilter {
+    pub from_date: Option<DateTimeAsMicroseconds>,
+    pub to_date: Option<DateTimeAsMicroseconds>,
+    pub close_reasons: Option<Vec<MtPositionCloseReason>>,
+}
+
+impl MtClosedPositionsFilter {
+    pub fn is_match(&self, position: &MtPosition<MtPositionClosedState>) -> bool {
+        let close_date = position.state.close_date.unix_microseconds;
+
+        if let Some(from_date) = self.from_date {
+            if close_date < from_date.unix_microseconds {
+                return false;
+            }
+        }
+
+        if let Some(to_date) = self.to_date {
+            if close_date > to_date.unix_microseconds {
+                return false;
+            }
+        }
+
+        if let Some(close_reasons) = &self.close_reasons {
+            return close_reasons.contains(&position.state.close_reason);
+        }
+
+        true
+    }
+}
+
+pub struct ClosedPositionsCache {
+    positions: PositionsCache<MtPosition<MtPositionClosedState>>,
+    pub capacity: Option<usize>,
+    pub retention_microseconds: Option<i64>,
+    close_dates: BTreeSet<(i64, String)>,
+}
+
+impl ClosedPositionsCache {
+    pub fn new(capacity: Option<usize>, retention_microseconds: Option<i64>) -> Self {
+        Self {
+            positions: PositionsCache::new("closed_positions".to_string()),
+            capacity,
+            retention_microseconds,
+            close_dates: BTreeSet::new(),
+        }
+    }
+
+    pub fn add_position(
+        &mut self,
+        position: MtPosition<MtPositionClosedState>,
+    ) -> Vec<MtPosition<MtPositionClosedState>> {
+        if self.positions.get_by_id(&position.base_data.id).is_some() {
+            self.remove_position(&position.base_data.id);
+        }
+
+        self.close_dates.insert((
+            position.state.close_date.unix_microseconds,
+            position.base_data.id.clone(),
+        ));
+        self.positions.add_position(position);
+
+        let mut evicted = vec![];
+
+        if let Some(capacity) = self.capacity {
+            while self.close_dates.len() > capacity {
+                let Some((_, id)) = self.close_dates.pop_first() else {
+                    break;
+                };
+
+                evicted.extend(self.remove_from_positions(&id));
+            }
+        }
+
+        evicted
+    }
+
+    pub fn remove_position(&mut self, id: &str) -> Option<MtPosition<MtPositionClosedState>> {
+        let position = self.remove_from_positions(id)?;
+        self.close_dates.remove(&(
+            position.state.close_date.unix_microseconds,
+            position.base_data.id.clone(),
+        ));
+
+        Some(position)
+    }
+
+    fn remove_from_positions(&mut self, id: &str) -> Option<MtPosition<MtPositionClosedState>> {
+        self.positions.get_by_id(id)?;
+        self.positions.remove_position(id)
+    }
+
+    pub fn evict_expired(
+        &mut self,
+        now: DateTimeAsMicroseconds,
+    ) -> Vec<MtPosition<MtPositionClosedState>> {
+        let Some(retention_microseconds) = self.retention_microseconds else {
+            return vec![];
+        };
+
+        let expire_before = now.unix_microseconds - retention_microseconds;
+
+        let mut evicted = vec![];
+
+        while let Some((close_date, _)) = self.close_dates.first() {
+            if *close_date >= expire_before {
+                break;
+            }
+
+            if let Some((_, id)) = self.close_dates.pop_first() {
+                evicted.extend(self.remove_from_positions(&id));
+            }
+        }
+
+        evicted
+    }
+
+    pub fn get_by_id(&self, id: &str) -> Option<&MtPosition<MtPositionClosedState>> {
+        self.positions.get_by_id(id)
+    }
+
+    pub fn query_positions(
+        &self,
+        query: EngineCacheQueryBuilder,
+    ) -> Vec<&MtPosition<MtPositionClosedState>> {
+        self.positions.query_positions(query)
+    }
+
+    pub fn query_history(
+        &self,
+        query: EngineCacheQueryBuilder,
+        filter: &MtClosedPositionsFilter,
+    ) -> Vec<&MtPosition<MtPositionClosedState>> {
+        let mut result: Vec<&MtPosition<MtPositionClosedState>> = self
+            .positions
+            .query_positions(query)
+            .into_iter()
+            .filter(|position| filter.is_match(position))
+            .collect();
+
+        result.sort_by(|a, b| {
+            b.state
+                .close_date
+                .unix_microseconds
+                .cmp(&a.state.close_date.unix_microseconds)
+        });
+
+        result
+    }
+
+    pub fn len(&self) -> usize {
+        self.close_dates.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.close_dates.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_extensions::date_time::DateTimeAsMicroseconds;
+    use trading_sdk_core::EngineCacheQueryBuilder;
+
+    use crate::{
+        convert_position_to_closed, ClosedPositionsCache, MtClosedPositionsFilter, MtPosition,
+        MtPositionCloseReason, TestEntity,
+    };
+
+    #[test]
+    fn test_query_history() {
+        let mut cache = ClosedPositionsCache::new(None, None);
+
+        for (id, close_date, close_reason) in [
+            ("1", 100, MtPositionCloseReason::StopLoss),
+            ("2", 200, MtPositionCloseReason::TakeProfit),
+            ("3", 300, MtPositionCloseReason::StopLoss),
+        ] {
+            let mut position = convert_position_to_closed(
+                MtPosition::generate_test_entity(),
+                close_reason,
+                "close".to_string(),
+            );
+            position.base_data.id = id.to_string();
+            position.state.close_date = DateTimeAsMicroseconds::new(close_date);
+            cache.add_position(position);
+        }
+
+        let all = cache.query_history(
+            EngineCacheQueryBuilder::new().with_account("account_id"),
+            &MtClosedPositionsFilter::default(),
+        );
+        let ids: Vec<&str> = all.iter().map(|x| x.base_data.id.as_str()).collect();
+        assert_eq!(ids, vec!["3", "2", "1"]);
+
+        let filtered = cache.query_history(
+            EngineCacheQueryBuilder::new().with_account("account_id"),
+            &MtClosedPositionsFilter {
+                from_date: Some(DateTimeAsMicroseconds::new(150)),
+                to_date: None,
+                close_reasons: Some(vec![MtPositionCloseReason::StopLoss]),
+            },
+        );
+        assert_eq!(filtered.len(), 1);
+        assert_eq!(filtered[0].base_data.id, "3");
+    }
+
+    #[test]
+    fn test_eviction() {
+        let mut cache = ClosedPositionsCache::new(Some(2), Some(150));
+        let mut position = convert_position_to_closed(
+            MtPosition::generate_test_entity(),
+            MtPositionCloseReason::StopLoss,
+            "close".to_string(),
+        );
+
+        position.base_data.id = "1".to_string();
+        position.state.close_date = DateTimeAsMicroseconds::new(100);
+        cache.add_position(position.clone());
+
+        position.base_data.id = "2".to_string();
+        position.state.close_date = DateTimeAsMicroseconds::new(300);
+        cache.add_position(position.clone());
+
+        position.base_data.id = "3".to_string();
+        position.state.close_date = DateTimeAsMicroseconds::new(200);
+        let evicted = cache.add_position(position.clone());
+        assert_eq!(evicted.len(), 1);
+        assert_eq!(evicted[0].base_data.id, "1");
+        assert_eq!(cache.len(), 2);
+
+        let evicted = cache.evict_expired(DateTimeAsMicroseconds::new(400));
+        assert_eq!(evicted.len(), 1);
+        assert_eq!(evicted[0].base_data.id, "3");
+        assert!(cache.get_by_id("2").is_some());
+
+        position.base_data.id = "2".to_string();
+        position.state.close_date = DateTimeAsMicroseconds::new(500);
+        cache.add_position(position);
+        assert_eq!(cache.len(), 1);
+        assert!(cache
+            .evict_expired(DateTimeAsMicroseconds::new(600))
+            .is_empty());
+    }
+}
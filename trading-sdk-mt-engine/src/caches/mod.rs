@@ -1,5 +1,17 @@
 mod mt_bid_ask_cache;
+mod mt_instruments_cache;
+mod mt_close_execution_policies_cache;
+mod mt_fee_models_cache;
+mod mt_exposure_book;
+mod mt_trading_sessions_cache;
+mod closed_positions_cache;
 pub use mt_bid_ask_cache::*;
+pub use mt_instruments_cache::*;
+pub use mt_close_execution_policies_cache::*;
+pub use mt_fee_models_cache::*;
+pub use mt_exposure_book::*;
+pub use mt_trading_sessions_cache::*;
+pub use closed_positions_cache::*;
 
 use trading_sdk_core::PositionsCache;
 
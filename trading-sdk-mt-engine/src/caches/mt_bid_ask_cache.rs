@@ -1,6 +1,6 @@
 use std::{collections::HashMap, sync::Arc};
 
-use crate::MtBidAsk;
+use crate::{normalize_bid_ask, MtBidAsk, MtInstrumentsCache};
 
 #[derive(Debug, Clone)]
 pub struct MtBidAskCache {
@@ -65,6 +65,17 @@ impl MtBidAskCache {
         quote_base.insert(bid_ask.base.clone(), bid_ask.clone());
     }
 
+    pub fn handle_new_normalized(
+        &mut self,
+        bid_ask: MtBidAsk,
+        instruments_cache: &MtInstrumentsCache,
+    ) {
+        match instruments_cache.get_by_id(&bid_ask.asset_pair) {
+            Some(instrument) => self.handle_new(normalize_bid_ask(&instrument, bid_ask)),
+            None => self.handle_new(bid_ask),
+        }
+    }
+
     pub fn get_by_id(&self, id: &str) -> Option<Arc<MtBidAsk>> {
         self.prices.get(id).cloned()
     }
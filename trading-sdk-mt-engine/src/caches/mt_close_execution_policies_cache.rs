@@ -0,0 +1,59 @@
+use std::collections::HashMap;
+
+use crate::MtCloseExecutionPolicy;
+
+#[derive(Debug, Clone)]
+pub struct MtCloseExecutionPoliciesCache {
+    policies: HashMap<String, MtCloseExecutionPolicy>,
+    default_policy: MtCloseExecutionPolicy,
+}
+
+impl MtCloseExecutionPoliciesCache {
+    pub fn new(default_policy: MtCloseExecutionPolicy) -> Self {
+        Self {
+            policies: HashMap::new(),
+            default_policy,
+        }
+    }
+
+    pub fn handle_new(&mut self, account_type: &str, policy: MtCloseExecutionPolicy) {
+        self.policies.insert(account_type.to_string(), policy);
+    }
+
+    pub fn remove(&mut self, account_type: &str) -> Option<MtCloseExecutionPolicy> {
+        self.policies.remove(account_type)
+    }
+
+    pub fn get_by_account_type(&self, account_type: &str) -> &MtCloseExecutionPolicy {
+        self.policies
+            .get(account_type)
+            .unwrap_or(&self.default_policy)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{MtCloseExecutionPoliciesCache, MtCloseExecutionPolicy};
+
+    #[test]
+    fn test_fallback_to_default() {
+        let mut cache = MtCloseExecutionPoliciesCache::new(MtCloseExecutionPolicy::Market);
+        cache.handle_new("pro", MtCloseExecutionPolicy::GuaranteedLevel);
+
+        assert!(matches!(
+            cache.get_by_account_type("pro"),
+            MtCloseExecutionPolicy::GuaranteedLevel
+        ));
+        assert!(matches!(
+            cache.get_by_account_type("standard"),
+            MtCloseExecutionPolicy::Market
+        ));
+
+        cache.remove("pro");
+
+        assert!(matches!(
+            cache.get_by_account_type("pro"),
+            MtCloseExecutionPolicy::Market
+        ));
+    }
+}
@@ -0,0 +1,240 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::{
+    get_position_exposure, update_exposure_rate, MtBidAsk, MtExposureTotals, MtPosition,
+    MtPositionActiveState, MtPositionExposure,
+};
+
+#[derive(Debug, Clone, Default)]
+pub struct MtExposureBook {
+    positions: HashMap<String, MtPositionExposure>,
+    asset_pair_positions: HashMap<String, HashSet<String>>,
+    totals: MtExposureTotals,
+    groups: HashMap<String, MtExposureTotals>,
+}
+
+impl<'a> FromIterator<&'a MtPosition<MtPositionActiveState>> for MtExposureBook {
+    fn from_iter<T: IntoIterator<Item = &'a MtPosition<MtPositionActiveState>>>(iter: T) -> Self {
+        let mut book = Self::new();
+
+        for position in iter {
+            book.handle_position_added(position);
+        }
+
+        book
+    }
+}
+
+impl MtExposureBook {
+    pub fn new() -> Self {
+        Self {
+            positions: HashMap::new(),
+            asset_pair_positions: HashMap::new(),
+            totals: MtExposureTotals::default(),
+            groups: HashMap::new(),
+        }
+    }
+
+    pub fn handle_position_added(&mut self, position: &MtPosition<MtPositionActiveState>) {
+        self.handle_position_removed(&position.base_data.id);
+        self.insert(get_position_exposure(position));
+    }
+
+    pub fn handle_position_updated(&mut self, position: &MtPosition<MtPositionActiveState>) {
+        self.handle_position_added(position);
+    }
+
+    pub fn handle_position_removed(&mut self, position_id: &str) -> Option<MtPositionExposure> {
+        let exposure = self.positions.remove(position_id)?;
+        self.totals.subtract(&exposure);
+
+        if let Some(position_ids) = self.asset_pair_positions.get_mut(&exposure.asset_pair) {
+            position_ids.remove(position_id);
+
+            if position_ids.is_empty() {
+                self.asset_pair_positions.remove(&exposure.asset_pair);
+            }
+        }
+
+        if let Some(group_id) = &exposure.group_id {
+            if let Some(group) = self.groups.get_mut(group_id) {
+                group.subtract(&exposure);
+
+                if group.pairs.is_empty() {
+                    self.groups.remove(group_id);
+                }
+            }
+        }
+
+        Some(exposure)
+    }
+
+    pub fn handle_bid_ask(&mut self, bid_ask: &MtBidAsk) {
+        let Some(position_ids) = self.asset_pair_positions.get(&bid_ask.asset_pair) else {
+            return;
+        };
+
+        let position_ids: Vec<String> = position_ids.iter().cloned().collect();
+
+        for position_id in position_ids {
+            let Some(mut exposure) = self.handle_position_removed(&position_id) else {
+                continue;
+            };
+
+            update_exposure_rate(&mut exposure, bid_ask);
+            self.insert(exposure);
+        }
+    }
+
+    pub fn get_totals(&self) -> &MtExposureTotals {
+        &self.totals
+    }
+
+    pub fn get_group(&self, group_id: &str) -> Option<&MtExposureTotals> {
+        self.groups.get(group_id)
+    }
+
+    pub fn get_groups(&self) -> &HashMap<String, MtExposureTotals> {
+        &self.groups
+    }
+
+    pub fn get_position(&self, position_id: &str) -> Option<&MtPositionExposure> {
+        self.positions.get(position_id)
+    }
+
+    fn insert(&mut self, exposure: MtPositionExposure) {
+        self.totals.add(&exposure);
+
+        if let Some(group_id) = &exposure.group_id {
+            self.groups
+                .entry(group_id.clone())
+                .or_default()
+                .add(&exposure);
+        }
+
+        self.asset_pair_positions
+            .entry(exposure.asset_pair.clone())
+            .or_default()
+            .insert(exposure.position_id.clone());
+
+        self.positions
+            .insert(exposure.position_id.clone(), exposure);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_extensions::date_time::DateTimeAsMicroseconds;
+
+    use crate::{
+        convert_currency_exposure, get_total_exposure_in_currency, MtBidAsk, MtBidAskCache,
+        MtEngineError, MtExposureBook, MtPosition, MtPositionActiveState, MtPositionSide,
+        TestEntity,
+    };
+
+    #[test]
+    fn test_exposure_add_remove() {
+        let mut book = MtExposureBook::new();
+        let mut position: MtPosition<MtPositionActiveState> = MtPosition::generate_test_entity();
+        position.base_data.asset_pair = "BASEQUOTE".to_string();
+        position.base_data.collateral = "base".to_string();
+        position.base_data.leverage = 10.0;
+        position.state.asset_active_price = 2.0;
+
+        for (id, side, group_id) in [
+            ("1", MtPositionSide::Buy, Some("vip")),
+            ("2", MtPositionSide::Sell, None),
+            ("3", MtPositionSide::Buy, None),
+        ] {
+            position.base_data.id = id.to_string();
+            position.base_data.side = side;
+            position.base_data.group_id = group_id.map(|x| x.to_string());
+            book.handle_position_added(&position);
+        }
+
+        let pair = book.get_totals().get_pair("BASEQUOTE").unwrap();
+        assert_eq!(pair.long_volume, 2000.0);
+        assert_eq!(pair.short_volume, 1000.0);
+        assert_eq!(pair.get_net_volume(), 1000.0);
+        assert_eq!(pair.get_house_net_volume(), -1000.0);
+
+        assert_eq!(book.get_totals().get_currency("base"), 1000.0);
+        assert_eq!(book.get_totals().get_currency("quote"), -2000.0);
+
+        let group = book.get_group("vip").unwrap();
+        assert_eq!(
+            group.get_pair("BASEQUOTE").unwrap().get_net_volume(),
+            1000.0
+        );
+
+        book.handle_position_removed("1");
+        assert!(book.get_group("vip").is_none());
+        assert_eq!(
+            book.get_totals()
+                .get_pair("BASEQUOTE")
+                .unwrap()
+                .get_net_volume(),
+            0.0
+        );
+        assert_eq!(book.get_totals().get_currency("base"), 0.0);
+
+        book.handle_position_removed("2");
+        book.handle_position_removed("3");
+        assert!(book.get_totals().get_pair("BASEQUOTE").is_none());
+        assert!(book.get_totals().currencies.is_empty());
+    }
+
+    #[test]
+    fn test_exposure_rate_update_and_conversion() {
+        let mut position: MtPosition<MtPositionActiveState> = MtPosition::generate_test_entity();
+        position.base_data.asset_pair = "BASEQUOTE".to_string();
+        position.base_data.collateral = "base".to_string();
+        position.base_data.leverage = 10.0;
+        position.state.asset_active_price = 2.0;
+        let positions = [position];
+        let mut book = MtExposureBook::from_iter(positions.iter());
+
+        let mut bid_ask = MtBidAsk::generate_test_entity();
+        bid_ask.bid = 3.0;
+        bid_ask.ask = 3.0;
+        book.handle_bid_ask(&bid_ask);
+
+        assert_eq!(book.get_totals().get_currency("base"), 1000.0);
+        assert_eq!(book.get_totals().get_currency("quote"), -3000.0);
+
+        let mut other_bid_ask = MtBidAsk::generate_test_entity();
+        other_bid_ask.asset_pair = "OTHERQUOTE".to_string();
+        other_bid_ask.bid = 5.0;
+        other_bid_ask.ask = 5.0;
+        book.handle_bid_ask(&other_bid_ask);
+
+        assert_eq!(book.get_totals().get_currency("quote"), -3000.0);
+
+        let prices_cache = MtBidAskCache::from_iter(vec![
+            MtBidAsk {
+                asset_pair: "QUOTEUSD".to_string(),
+                bid: 0.5,
+                ask: 0.5,
+                base: "quote".to_string(),
+                quote: "USD".to_string(),
+                date: DateTimeAsMicroseconds::now(),
+            },
+            bid_ask,
+        ]);
+
+        let converted =
+            convert_currency_exposure(book.get_totals(), "quote", &prices_cache).unwrap();
+        assert_eq!(converted["base"], 3000.0);
+        assert_eq!(converted["quote"], -3000.0);
+
+        assert!(matches!(
+            convert_currency_exposure(book.get_totals(), "USD", &prices_cache),
+            Err(MtEngineError::NoConversionLiquidity { .. })
+        ));
+
+        assert_eq!(
+            get_total_exposure_in_currency(book.get_totals(), "quote", &prices_cache).unwrap(),
+            6000.0
+        );
+    }
+}
@@ -0,0 +1,85 @@
+use std::collections::HashMap;
+
+use crate::MtFeeModel;
+
+#[derive(Debug, Clone, Default)]
+pub struct MtFeeModelsCache {
+    instrument_models: HashMap<String, MtFeeModel>,
+    group_models: HashMap<(String, String), MtFeeModel>,
+}
+
+impl MtFeeModelsCache {
+    pub fn new() -> Self {
+        Self {
+            instrument_models: HashMap::new(),
+            group_models: HashMap::new(),
+        }
+    }
+
+    pub fn handle_new(&mut self, instrument_id: &str, group_id: Option<&str>, model: MtFeeModel) {
+        match group_id {
+            Some(group_id) => {
+                self.group_models
+                    .insert((instrument_id.to_string(), group_id.to_string()), model);
+            }
+            None => {
+                self.instrument_models
+                    .insert(instrument_id.to_string(), model);
+            }
+        }
+    }
+
+    pub fn remove(&mut self, instrument_id: &str, group_id: Option<&str>) -> Option<MtFeeModel> {
+        match group_id {
+            Some(group_id) => self
+                .group_models
+                .remove(&(instrument_id.to_string(), group_id.to_string())),
+            None => self.instrument_models.remove(instrument_id),
+        }
+    }
+
+    pub fn get(&self, instrument_id: &str, group_id: Option<&str>) -> Option<&MtFeeModel> {
+        if let Some(group_id) = group_id {
+            let group_model = self
+                .group_models
+                .get(&(instrument_id.to_string(), group_id.to_string()));
+
+            if group_model.is_some() {
+                return group_model;
+            }
+        }
+
+        self.instrument_models.get(instrument_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{MtFeeCalculationType, MtFeeChargeType, MtFeeModel, MtFeeModelsCache};
+
+    fn fixed(amount: f64) -> MtFeeModel {
+        MtFeeModel {
+            calculation_type: MtFeeCalculationType::Fixed { amount },
+            charge_type: MtFeeChargeType::Open,
+        }
+    }
+
+    #[test]
+    fn test_group_overrides_instrument() {
+        let mut cache = MtFeeModelsCache::new();
+        cache.handle_new("EURUSD", None, fixed(1.0));
+        cache.handle_new("EURUSD", Some("vip"), fixed(0.5));
+
+        let fee = |group_id| {
+            cache
+                .get("EURUSD", group_id)
+                .unwrap()
+                .calculate_fee(0.0, 0.0)
+        };
+
+        assert_eq!(fee(None), 1.0);
+        assert_eq!(fee(Some("vip")), 0.5);
+        assert_eq!(fee(Some("standard")), 1.0);
+        assert!(cache.get("GBPUSD", Some("vip")).is_none());
+    }
+}
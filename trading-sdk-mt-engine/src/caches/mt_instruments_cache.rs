@@ -0,0 +1,72 @@
+use std::{collections::HashMap, sync::Arc};
+
+use crate::MtInstrument;
+
+#[derive(Debug, Clone, Default)]
+pub struct MtInstrumentsCache {
+    instruments: HashMap<String, Arc<MtInstrument>>,
+}
+
+impl FromIterator<MtInstrument> for MtInstrumentsCache {
+    fn from_iter<T: IntoIterator<Item = MtInstrument>>(iter: T) -> Self {
+        let mut instruments = HashMap::new();
+
+        for instrument in iter {
+            instruments.insert(instrument.id.clone(), Arc::new(instrument));
+        }
+
+        Self { instruments }
+    }
+}
+
+impl MtInstrumentsCache {
+    pub fn new() -> Self {
+        Self {
+            instruments: HashMap::new(),
+        }
+    }
+
+    pub fn handle_new(&mut self, instrument: MtInstrument) {
+        self.instruments
+            .insert(instrument.id.clone(), Arc::new(instrument));
+    }
+
+    pub fn remove(&mut self, id: &str) -> Option<Arc<MtInstrument>> {
+        self.instruments.remove(id)
+    }
+
+    pub fn get_by_id(&self, id: &str) -> Option<Arc<MtInstrument>> {
+        self.instruments.get(id).cloned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{MtInstrument, MtInstrumentsCache, TestEntity};
+
+    #[test]
+    fn test_cache_handle_update() {
+        let mut cache = MtInstrumentsCache::new();
+
+        let mut instrument = MtInstrument::generate_test_entity();
+        instrument.min_invest_amount = Some(10.0);
+        cache.handle_new(instrument.clone());
+
+        assert_eq!(
+            cache.get_by_id("BASEQUOTE").unwrap().min_invest_amount,
+            Some(10.0)
+        );
+
+        instrument.min_invest_amount = Some(20.0);
+        cache.handle_new(instrument);
+
+        assert_eq!(
+            cache.get_by_id("BASEQUOTE").unwrap().min_invest_amount,
+            Some(20.0)
+        );
+
+        cache.remove("BASEQUOTE");
+
+        assert!(cache.get_by_id("BASEQUOTE").is_none());
+    }
+}
@@ -0,0 +1,119 @@
+use std::collections::HashMap;
+
+use crate::{is_instrument_session_open, MtBidAsk, MtInstrument, MtSessionTick};
+
+#[derive(Debug, Clone)]
+struct MtSessionState {
+    is_open: bool,
+    last_open_bid_ask: Option<MtBidAsk>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct MtTradingSessionsCache {
+    states: HashMap<String, MtSessionState>,
+}
+
+impl MtTradingSessionsCache {
+    pub fn new() -> Self {
+        Self {
+            states: HashMap::new(),
+        }
+    }
+
+    pub fn handle_tick(&mut self, instrument: &MtInstrument, bid_ask: &MtBidAsk) -> MtSessionTick {
+        let is_open = is_instrument_session_open(instrument, bid_ask.date);
+
+        let state = self
+            .states
+            .entry(bid_ask.asset_pair.clone())
+            .or_insert_with(|| MtSessionState {
+                is_open,
+                last_open_bid_ask: None,
+            });
+
+        let is_reopened = is_open && !state.is_open;
+
+        let gap = match (is_reopened, &state.last_open_bid_ask) {
+            (true, Some(last_bid_ask)) => {
+                Some(get_mid_price(bid_ask) - get_mid_price(last_bid_ask))
+            }
+            _ => None,
+        };
+
+        state.is_open = is_open;
+
+        if is_open {
+            state.last_open_bid_ask = Some(bid_ask.clone());
+        }
+
+        MtSessionTick {
+            asset_pair: bid_ask.asset_pair.clone(),
+            is_open,
+            is_reopened,
+            gap,
+        }
+    }
+
+    pub fn is_open(&self, asset_pair: &str) -> Option<bool> {
+        self.states.get(asset_pair).map(|state| state.is_open)
+    }
+}
+
+fn get_mid_price(bid_ask: &MtBidAsk) -> f64 {
+    (bid_ask.bid + bid_ask.ask) / 2.0
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_extensions::date_time::DateTimeAsMicroseconds;
+
+    use crate::{
+        MtBidAsk, MtInstrument, MtTradingSchedule, MtTradingSessionWindow, MtTradingSessionsCache,
+        TestEntity,
+    };
+
+    fn create_bid_ask(price: f64, value: &str) -> MtBidAsk {
+        let date = chrono::DateTime::parse_from_rfc3339(value).unwrap();
+        let mut bid_ask = MtBidAsk::generate_test_entity();
+        bid_ask.bid = price;
+        bid_ask.ask = price;
+        bid_ask.date = DateTimeAsMicroseconds::new(date.timestamp_micros());
+        bid_ask
+    }
+
+    #[test]
+    fn test_reopen_gap() {
+        let mut instrument = MtInstrument::generate_test_entity();
+        instrument.trading_schedule = Some(MtTradingSchedule {
+            timezone: "UTC".to_string(),
+            windows: (0..5)
+                .map(|weekday| MtTradingSessionWindow {
+                    weekday,
+                    open_minute: 0,
+                    close_minute: 24 * 60,
+                })
+                .collect(),
+            daily_breaks: vec![],
+            holidays: vec![],
+        });
+
+        let mut cache = MtTradingSessionsCache::new();
+
+        let tick = cache.handle_tick(&instrument, &create_bid_ask(25.0, "2026-10-16T20:00:00Z"));
+        assert!(tick.is_open);
+        assert!(!tick.is_reopened);
+
+        let tick = cache.handle_tick(&instrument, &create_bid_ask(26.0, "2026-10-17T10:00:00Z"));
+        assert!(!tick.is_open);
+        assert_eq!(cache.is_open("BASEQUOTE"), Some(false));
+
+        let tick = cache.handle_tick(&instrument, &create_bid_ask(27.0, "2026-10-19T00:01:00Z"));
+        assert!(tick.is_open);
+        assert!(tick.is_reopened);
+        assert_eq!(tick.gap, Some(2.0));
+
+        let tick = cache.handle_tick(&instrument, &create_bid_ask(27.5, "2026-10-19T00:02:00Z"));
+        assert!(!tick.is_reopened);
+        assert_eq!(tick.gap, None);
+    }
+}
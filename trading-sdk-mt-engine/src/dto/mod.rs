@@ -7,6 +7,22 @@ mod mt_position_closed_state;
 mod mt_position_swap;
 mod mt_engine_error;
 mod mt_position_close_reason;
+mod mt_instrument;
+mod mt_position_protection;
+mod mt_position_cancel_reason;
+mod mt_position_canceled_state;
+mod mt_close_execution_policy;
+mod mt_fee_model;
+mod mt_position_fees;
+mod mt_account_mode;
+mod mt_margin_call;
+mod mt_amount_rounding;
+mod mt_off_tick_policy;
+mod mt_exposure;
+mod mt_hedging;
+mod mt_trading_session;
+mod mt_instrument_status;
+mod mt_trading_statistics;
 
 pub use mt_position::*;
 pub use mt_bid_ask::*;
@@ -16,4 +32,20 @@ pub use mt_position_pending_state::*;
 pub use mt_position_closed_state::*;
 pub use mt_position_swap::*;
 pub use mt_engine_error::*;
-pub use mt_position_close_reason::*;
\ No newline at end of file
+pub use mt_position_close_reason::*;
+pub use mt_instrument::*;
+pub use mt_position_protection::*;
+pub use mt_position_cancel_reason::*;
+pub use mt_position_canceled_state::*;
+pub use mt_close_execution_policy::*;
+pub use mt_fee_model::*;
+pub use mt_position_fees::*;
+pub use mt_account_mode::*;
+pub use mt_margin_call::*;
+pub use mt_amount_rounding::*;
+pub use mt_off_tick_policy::*;
+pub use mt_exposure::*;
+pub use mt_hedging::*;
+pub use mt_trading_session::*;
+pub use mt_instrument_status::*;
+pub use mt_trading_statistics::*;
\ No newline at end of file
@@ -0,0 +1,7 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum MtAccountMode {
+    Hedging = 0,
+    Netting = 1,
+}
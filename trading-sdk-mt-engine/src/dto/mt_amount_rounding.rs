@@ -0,0 +1,15 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum MtRoundingMode {
+    HalfUp = 0,
+    HalfEven = 1,
+    Down = 2,
+    Up = 3,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MtAmountRounding {
+    pub decimals: u32,
+    pub mode: MtRoundingMode,
+}
@@ -0,0 +1,8 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum MtCloseExecutionPolicy {
+    Market = 0,
+    GuaranteedLevel = 1,
+    TakeProfitLevelCappedByMarket = 2,
+}
@@ -1,7 +1,290 @@
-use serde::{Serialize, Deserialize};
+use std::fmt::Display;
+
+use serde::{Deserialize, Serialize};
+
+use crate::MtInstrumentStatus;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum MtValidationViolation {
+    InstrumentStatusRestricted {
+        status: MtInstrumentStatus,
+    },
+    AssetPairMismatch {
+        expected_base: String,
+        expected_quote: String,
+        base: String,
+        quote: String,
+    },
+    InvestAmountTooLow {
+        min: f64,
+        actual: f64,
+    },
+    InvestAmountTooHigh {
+        max: f64,
+        actual: f64,
+    },
+    LeverageNotAllowed {
+        allowed: Vec<f64>,
+        actual: f64,
+    },
+    PriceNotOnTick {
+        price: f64,
+        tick_size: f64,
+    },
+    StopLossPriceInvalid {
+        price: f64,
+        reference_price: f64,
+    },
+    TakeProfitPriceInvalid {
+        price: f64,
+        reference_price: f64,
+    },
+    StopLossProfitInvalid {
+        profit: f64,
+        current_profit: f64,
+    },
+    TakeProfitProfitInvalid {
+        profit: f64,
+        current_profit: f64,
+    },
+    PendingTriggerPriceInvalid {
+        price: f64,
+        current_price: f64,
+    },
+    PendingLimitPriceInvalid {
+        limit_price: f64,
+        trigger_price: f64,
+    },
+    BracketDistanceInvalid {
+        distance: f64,
+    },
+    BracketLevelConflict,
+    MarketClosed,
+}
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum MtEngineError {
-    NoLiquidity,
-    PositionNotFound,
+    NoLiquidity {
+        asset_pair: String,
+    },
+    NoConversionLiquidity {
+        asset: String,
+        collateral: String,
+    },
+    StalePrice {
+        asset_pair: String,
+        age_microseconds: i64,
+    },
+    PositionNotFound {
+        position_id: String,
+    },
+    InstrumentNotFound {
+        asset_pair: String,
+    },
+    ValidationFailed {
+        position_id: String,
+        violation: MtValidationViolation,
+    },
+    Requote {
+        asset_pair: String,
+        expected_price: f64,
+        actual_price: f64,
+    },
+    ToppingUpNotSet {
+        position_id: String,
+    },
+    InvalidToppingUpAmount {
+        position_id: String,
+        amount: f64,
+    },
+    LiquidityProviderRejected {
+        asset_pair: String,
+        reason: String,
+    },
+    InvalidAmount {
+        value: f64,
+    },
+    AmountDivisionByZero,
+    AmountOverflow,
+}
+
+impl MtEngineError {
+    pub fn validation_failed(position_id: &str, violation: MtValidationViolation) -> Self {
+        Self::ValidationFailed {
+            position_id: position_id.to_string(),
+            violation,
+        }
+    }
+}
+
+impl Display for MtValidationViolation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MtValidationViolation::InstrumentStatusRestricted { status } => {
+                write!(f, "instrument status {status:?} does not allow opening")
+            }
+            MtValidationViolation::AssetPairMismatch {
+                expected_base,
+                expected_quote,
+                base,
+                quote,
+            } => write!(
+                f,
+                "asset pair {base}/{quote} does not match {expected_base}/{expected_quote}"
+            ),
+            MtValidationViolation::InvestAmountTooLow { min, actual } => {
+                write!(f, "invest amount {actual} is lower than {min}")
+            }
+            MtValidationViolation::InvestAmountTooHigh { max, actual } => {
+                write!(f, "invest amount {actual} is higher than {max}")
+            }
+            MtValidationViolation::LeverageNotAllowed { allowed, actual } => {
+                write!(f, "leverage {actual} is not one of {allowed:?}")
+            }
+            MtValidationViolation::PriceNotOnTick { price, tick_size } => {
+                write!(
+                    f,
+                    "price {price} is not a multiple of tick size {tick_size}"
+                )
+            }
+            MtValidationViolation::StopLossPriceInvalid {
+                price,
+                reference_price,
+            } => write!(
+                f,
+                "stop loss price {price} is on the wrong side of {reference_price}"
+            ),
+            MtValidationViolation::TakeProfitPriceInvalid {
+                price,
+                reference_price,
+            } => write!(
+                f,
+                "take profit price {price} is on the wrong side of {reference_price}"
+            ),
+            MtValidationViolation::StopLossProfitInvalid {
+                profit,
+                current_profit,
+            } => write!(
+                f,
+                "stop loss profit {profit} is not below current profit {current_profit}"
+            ),
+            MtValidationViolation::TakeProfitProfitInvalid {
+                profit,
+                current_profit,
+            } => write!(
+                f,
+                "take profit profit {profit} is not above current profit {current_profit}"
+            ),
+            MtValidationViolation::PendingTriggerPriceInvalid {
+                price,
+                current_price,
+            } => write!(
+                f,
+                "trigger price {price} is on the wrong side of current price {current_price}"
+            ),
+            MtValidationViolation::PendingLimitPriceInvalid {
+                limit_price,
+                trigger_price,
+            } => write!(
+                f,
+                "limit price {limit_price} is on the wrong side of trigger price {trigger_price}"
+            ),
+            MtValidationViolation::BracketDistanceInvalid { distance } => {
+                write!(f, "bracket distance {distance} must be positive")
+            }
+            MtValidationViolation::BracketLevelConflict => {
+                write!(
+                    f,
+                    "bracket distance conflicts with an absolute protection level"
+                )
+            }
+            MtValidationViolation::MarketClosed => write!(f, "market is closed"),
+        }
+    }
+}
+
+impl Display for MtEngineError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MtEngineError::NoLiquidity { asset_pair } => {
+                write!(f, "No liquidity for {asset_pair}")
+            }
+            MtEngineError::NoConversionLiquidity { asset, collateral } => {
+                write!(f, "No liquidity to convert {asset} to {collateral}")
+            }
+            MtEngineError::StalePrice {
+                asset_pair,
+                age_microseconds,
+            } => write!(
+                f,
+                "Price for {asset_pair} is stale: {age_microseconds} microseconds old"
+            ),
+            MtEngineError::PositionNotFound { position_id } => {
+                write!(f, "Position {position_id} not found")
+            }
+            MtEngineError::InstrumentNotFound { asset_pair } => {
+                write!(f, "Instrument {asset_pair} not found")
+            }
+            MtEngineError::ValidationFailed {
+                position_id,
+                violation,
+            } => write!(f, "Position {position_id} validation failed: {violation}"),
+            MtEngineError::Requote {
+                asset_pair,
+                expected_price,
+                actual_price,
+            } => write!(
+                f,
+                "Requote for {asset_pair}: expected {expected_price}, actual {actual_price}"
+            ),
+            MtEngineError::ToppingUpNotSet { position_id } => {
+                write!(f, "Position {position_id} has no topping up to return")
+            }
+            MtEngineError::InvalidToppingUpAmount {
+                position_id,
+                amount,
+            } => write!(
+                f,
+                "Invalid topping up amount {amount} for position {position_id}"
+            ),
+            MtEngineError::LiquidityProviderRejected { asset_pair, reason } => {
+                write!(f, "Liquidity provider rejected {asset_pair}: {reason}")
+            }
+            MtEngineError::InvalidAmount { value } => {
+                write!(f, "Amount {value} cannot be represented")
+            }
+            MtEngineError::AmountDivisionByZero => write!(f, "Amount division by zero"),
+            MtEngineError::AmountOverflow => write!(f, "Amount overflow"),
+        }
+    }
+}
+
+impl std::error::Error for MtEngineError {}
+
+#[cfg(test)]
+mod tests {
+    use crate::{MtEngineError, MtValidationViolation};
+
+    #[test]
+    fn test_display() {
+        let error = MtEngineError::NoConversionLiquidity {
+            asset: "GBP".to_string(),
+            collateral: "USD".to_string(),
+        };
+
+        assert_eq!(error.to_string(), "No liquidity to convert GBP to USD");
+
+        let error = MtEngineError::validation_failed(
+            "id",
+            MtValidationViolation::InvestAmountTooLow {
+                min: 10.0,
+                actual: 5.0,
+            },
+        );
+
+        assert_eq!(
+            error.to_string(),
+            "Position id validation failed: invest amount 5 is lower than 10"
+        );
+    }
 }
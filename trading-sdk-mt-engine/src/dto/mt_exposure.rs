@@ -0,0 +1,113 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::MtPositionSide;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MtPositionExposure {
+    pub position_id: String,
+    pub asset_pair: String,
+    pub base: String,
+    pub quote: String,
+    pub side: MtPositionSide,
+    pub group_id: Option<String>,
+    pub base_amount: f64,
+    pub quote_amount: f64,
+}
+
+impl MtPositionExposure {
+    pub fn get_base_volume(&self) -> f64 {
+        self.base_amount.abs()
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MtPairExposure {
+    pub long_volume: f64,
+    pub short_volume: f64,
+    pub positions_count: usize,
+}
+
+impl MtPairExposure {
+    pub fn get_net_volume(&self) -> f64 {
+        self.long_volume - self.short_volume
+    }
+
+    pub fn get_house_net_volume(&self) -> f64 {
+        -self.get_net_volume()
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MtExposureTotals {
+    pub pairs: HashMap<String, MtPairExposure>,
+    pub currencies: HashMap<String, f64>,
+    pub currency_positions_count: HashMap<String, usize>,
+}
+
+impl MtExposureTotals {
+    pub fn add(&mut self, exposure: &MtPositionExposure) {
+        let pair = self.pairs.entry(exposure.asset_pair.clone()).or_default();
+
+        match exposure.side {
+            MtPositionSide::Buy => pair.long_volume += exposure.get_base_volume(),
+            MtPositionSide::Sell => pair.short_volume += exposure.get_base_volume(),
+        }
+        pair.positions_count += 1;
+
+        self.add_currency(&exposure.base, exposure.base_amount);
+        self.add_currency(&exposure.quote, exposure.quote_amount);
+    }
+
+    pub fn subtract(&mut self, exposure: &MtPositionExposure) {
+        if let Some(pair) = self.pairs.get_mut(&exposure.asset_pair) {
+            match exposure.side {
+                MtPositionSide::Buy => pair.long_volume -= exposure.get_base_volume(),
+                MtPositionSide::Sell => pair.short_volume -= exposure.get_base_volume(),
+            }
+            pair.positions_count -= 1;
+
+            if pair.positions_count == 0 {
+                self.pairs.remove(&exposure.asset_pair);
+            }
+        }
+
+        self.subtract_currency(&exposure.base, exposure.base_amount);
+        self.subtract_currency(&exposure.quote, exposure.quote_amount);
+    }
+
+    pub fn get_pair(&self, asset_pair: &str) -> Option<&MtPairExposure> {
+        self.pairs.get(asset_pair)
+    }
+
+    pub fn get_currency(&self, currency: &str) -> f64 {
+        self.currencies.get(currency).copied().unwrap_or(0.0)
+    }
+
+    fn add_currency(&mut self, currency: &str, amount: f64) {
+        *self.currencies.entry(currency.to_string()).or_default() += amount;
+        *self
+            .currency_positions_count
+            .entry(currency.to_string())
+            .or_default() += 1;
+    }
+
+    fn subtract_currency(&mut self, currency: &str, amount: f64) {
+        let Some(count) = self.currency_positions_count.get_mut(currency) else {
+            return;
+        };
+
+        *count -= 1;
+
+        if *count == 0 {
+            self.currency_positions_count.remove(currency);
+            self.currencies.remove(currency);
+            return;
+        }
+
+        if let Some(total) = self.currencies.get_mut(currency) {
+            *total -= amount;
+        }
+    }
+}
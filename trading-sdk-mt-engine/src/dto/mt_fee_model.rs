@@ -0,0 +1,70 @@
+use serde::{Deserialize, Serialize};
+
+use crate::MtPositionFees;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum MtFeeCalculationType {
+    PerLot {
+        contract_size: f64,
+        amount_per_lot: f64,
+    },
+    NotionalPercent {
+        percent: f64,
+    },
+    Fixed {
+        amount: f64,
+    },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum MtFeeChargeType {
+    Open = 0,
+    Close = 1,
+    Both = 2,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MtFeeModel {
+    pub calculation_type: MtFeeCalculationType,
+    pub charge_type: MtFeeChargeType,
+}
+
+impl MtFeeModel {
+    pub fn calculate_fee(&self, notional: f64, base_volume: f64) -> f64 {
+        match &self.calculation_type {
+            MtFeeCalculationType::PerLot {
+                contract_size,
+                amount_per_lot,
+            } => base_volume / contract_size * amount_per_lot,
+            MtFeeCalculationType::NotionalPercent { percent } => notional * percent / 100.0,
+            MtFeeCalculationType::Fixed { amount } => *amount,
+        }
+    }
+
+    pub fn calculate_position_fees(&self, notional: f64, base_volume: f64) -> MtPositionFees {
+        let fee = self.calculate_fee(notional, base_volume);
+
+        let close_fee_percent = match &self.calculation_type {
+            MtFeeCalculationType::NotionalPercent { percent } => Some(*percent),
+            _ => None,
+        };
+
+        match self.charge_type {
+            MtFeeChargeType::Open => MtPositionFees {
+                open_fee: fee,
+                close_fee: 0.0,
+                close_fee_percent: None,
+            },
+            MtFeeChargeType::Close => MtPositionFees {
+                open_fee: 0.0,
+                close_fee: fee,
+                close_fee_percent,
+            },
+            MtFeeChargeType::Both => MtPositionFees {
+                open_fee: fee,
+                close_fee: fee,
+                close_fee_percent,
+            },
+        }
+    }
+}
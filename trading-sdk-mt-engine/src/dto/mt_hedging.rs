@@ -0,0 +1,72 @@
+use rust_extensions::date_time::DateTimeAsMicroseconds;
+use serde::{Deserialize, Serialize};
+
+use crate::MtPositionSide;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MtHedgeLimit {
+    pub max_net_volume: f64,
+    pub target_net_volume: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MtHedgeOrder {
+    pub id: String,
+    pub asset_pair: String,
+    pub side: MtPositionSide,
+    pub volume: f64,
+    pub requested_price: f64,
+    pub date: DateTimeAsMicroseconds,
+}
+
+impl MtHedgeOrder {
+    pub fn get_signed_volume(&self) -> f64 {
+        match self.side {
+            MtPositionSide::Buy => self.volume,
+            MtPositionSide::Sell => -self.volume,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MtHedgePosition {
+    pub id: String,
+    pub asset_pair: String,
+    pub side: MtPositionSide,
+    pub volume: f64,
+    pub open_price: f64,
+    pub active_price: f64,
+    pub open_date: DateTimeAsMicroseconds,
+}
+
+impl MtHedgePosition {
+    pub fn get_signed_volume(&self) -> f64 {
+        match self.side {
+            MtPositionSide::Buy => self.volume,
+            MtPositionSide::Sell => -self.volume,
+        }
+    }
+
+    pub fn get_profit(&self) -> f64 {
+        (self.active_price - self.open_price) * self.get_signed_volume()
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MtHedgeFillResult {
+    pub order_id: String,
+    pub reduced_volume: f64,
+    pub realized_pnl: f64,
+    pub opened_position: Option<MtHedgePosition>,
+}
+
+/// All PnL values are in the quote currency of the asset pair.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MtHedgingPnlReport {
+    pub asset_pair: String,
+    pub client_net_volume: f64,
+    pub hedged_net_volume: f64,
+    pub unhedged_pnl: f64,
+    pub hedge_pnl: f64,
+    pub hedged_pnl: f64,
+}
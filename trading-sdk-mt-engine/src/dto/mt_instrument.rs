@@ -0,0 +1,42 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{MtAmountRounding, MtInstrumentStatus, MtOffTickPolicy, MtTradingSchedule, TestEntity};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MtInstrument {
+    pub id: String,
+    pub base: String,
+    pub quote: String,
+    pub min_invest_amount: Option<f64>,
+    pub max_invest_amount: Option<f64>,
+    pub allowed_leverages: Option<Vec<f64>>,
+    pub tick_size: Option<f64>,
+    pub digits: Option<u32>,
+    pub off_tick_policy: MtOffTickPolicy,
+    pub status: MtInstrumentStatus,
+    pub max_price_age_microseconds: Option<i64>,
+    pub allow_positive_slippage: bool,
+    pub amount_rounding: Option<MtAmountRounding>,
+    pub trading_schedule: Option<MtTradingSchedule>,
+}
+
+impl TestEntity for MtInstrument {
+    fn generate_test_entity() -> Self {
+        Self {
+            id: "BASEQUOTE".to_string(),
+            base: "base".to_string(),
+            quote: "quote".to_string(),
+            min_invest_amount: None,
+            max_invest_amount: None,
+            allowed_leverages: None,
+            tick_size: None,
+            digits: None,
+            off_tick_policy: MtOffTickPolicy::Reject,
+            status: MtInstrumentStatus::Enabled,
+            max_price_age_microseconds: None,
+            allow_positive_slippage: true,
+            amount_rounding: None,
+            trading_schedule: None,
+        }
+    }
+}
@@ -0,0 +1,19 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum MtInstrumentStatus {
+    Enabled,
+    CloseOnly,
+    Halted,
+    Delisting { reference_price: f64 },
+}
+
+impl MtInstrumentStatus {
+    pub fn is_open_allowed(&self) -> bool {
+        matches!(self, MtInstrumentStatus::Enabled)
+    }
+
+    pub fn is_evaluation_allowed(&self) -> bool {
+        !matches!(self, MtInstrumentStatus::Halted)
+    }
+}
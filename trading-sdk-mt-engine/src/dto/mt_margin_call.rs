@@ -0,0 +1,45 @@
+use rust_extensions::date_time::DateTimeAsMicroseconds;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum MtMarginCallRecoveryRule {
+    Threshold { recovery_percent: f64 },
+    Band { band_percent: f64 },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MtMarginCallSettings {
+    pub recovery_rule: MtMarginCallRecoveryRule,
+    pub renotify_interval_microseconds: Option<i64>,
+}
+
+impl MtMarginCallSettings {
+    pub fn get_recovery_percent(&self, margin_call_percent: f64) -> f64 {
+        match self.recovery_rule {
+            MtMarginCallRecoveryRule::Threshold { recovery_percent } => recovery_percent,
+            MtMarginCallRecoveryRule::Band { band_percent } => margin_call_percent - band_percent,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MtMarginCallNotificationState {
+    pub is_notified_hit: bool,
+    pub last_notification_date: Option<DateTimeAsMicroseconds>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum MtMarginCallEvent {
+    MarginCallEntered {
+        position_id: String,
+        account_id: String,
+        margin_used_percent: f64,
+        date: DateTimeAsMicroseconds,
+    },
+    MarginCallRecovered {
+        position_id: String,
+        account_id: String,
+        margin_used_percent: f64,
+        date: DateTimeAsMicroseconds,
+    },
+}
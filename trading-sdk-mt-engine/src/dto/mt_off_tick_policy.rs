@@ -0,0 +1,7 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum MtOffTickPolicy {
+    Reject = 0,
+    Round = 1,
+}
@@ -1,7 +1,10 @@
 use rust_extensions::date_time::DateTimeAsMicroseconds;
 use serde::{Deserialize, Serialize};
 
-use crate::{MtBidAsk, MtPositionPendingState, MtPositionSwaps, TestEntity};
+use crate::{
+    MtBidAsk, MtMarginCallNotificationState, MtPositionFees, MtPositionPendingState,
+    MtPositionSwaps, TestEntity,
+};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MtPositionActiveStateOpenData {
@@ -25,6 +28,8 @@ pub struct MtPositionActiveState {
     pub swaps: MtPositionSwaps,
     pub topping_up: Option<f64>,
     pub is_margin_call_hit: bool,
+    pub fees: MtPositionFees,
+    pub margin_call_notification: MtMarginCallNotificationState,
 }
 
 impl TestEntity for MtPositionActiveStateOpenData {
@@ -53,6 +58,8 @@ impl TestEntity for MtPositionActiveState {
             swaps: MtPositionSwaps::default(),
             topping_up: None,
             is_margin_call_hit: false,
+            fees: MtPositionFees::default(),
+            margin_call_notification: MtMarginCallNotificationState::default(),
         }
     }
 }
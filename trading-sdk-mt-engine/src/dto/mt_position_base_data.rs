@@ -5,7 +5,7 @@ use serde::{Deserialize, Serialize};
 
 use crate::TestEntity;
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum MtPositionSide {
     Buy,
     Sell,
@@ -35,6 +35,7 @@ pub struct MtPositionBaseData {
     pub margin_call_percent: Option<f64>,
     pub topping_up_percent: Option<f64>,
     pub metadata: Option<HashMap<String, String>>,
+    pub group_id: Option<String>,
 }
 
 impl TestEntity for MtPositionBaseData {
@@ -62,6 +63,7 @@ impl TestEntity for MtPositionBaseData {
             margin_call_percent: None,
             topping_up_percent: None,
             metadata: None,
+            group_id: None,
         }
     }
 }
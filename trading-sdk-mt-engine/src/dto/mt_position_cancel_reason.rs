@@ -0,0 +1,9 @@
+use serde::{Serialize, Deserialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum MtPositionCancelReason {
+    ClientCommand = 0,
+    Expired = 1,
+    ForceCancel = 2,
+    OcoSiblingExecuted = 3,
+}
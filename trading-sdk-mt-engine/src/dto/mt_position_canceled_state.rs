@@ -0,0 +1,12 @@
+use rust_extensions::date_time::DateTimeAsMicroseconds;
+use serde::{Deserialize, Serialize};
+
+use crate::{MtPositionCancelReason, MtPositionPendingState};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MtPositionCanceledState {
+    pub pending_state: MtPositionPendingState,
+    pub cancel_reason: MtPositionCancelReason,
+    pub cancel_process_id: String,
+    pub cancel_date: DateTimeAsMicroseconds,
+}
@@ -1,10 +1,11 @@
 use serde::{Serialize, Deserialize};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum MtPositionCloseReason{
     ClientCommand = 0,
     StopOut = 1,
     TakeProfit = 2,
     StopLoss = 3,
     ForceClose = 4,
+    Netting = 5,
 }
\ No newline at end of file
@@ -0,0 +1,24 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MtPositionFees {
+    pub open_fee: f64,
+    pub close_fee: f64,
+    pub close_fee_percent: Option<f64>,
+}
+
+impl Default for MtPositionFees {
+    fn default() -> Self {
+        Self {
+            open_fee: 0.0,
+            close_fee: 0.0,
+            close_fee_percent: None,
+        }
+    }
+}
+
+impl MtPositionFees {
+    pub fn get_total(&self) -> f64 {
+        self.open_fee + self.close_fee
+    }
+}
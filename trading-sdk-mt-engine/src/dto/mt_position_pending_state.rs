@@ -1,4 +1,6 @@
-use serde::{Serialize, Deserialize};
+use serde::{Deserialize, Serialize};
+
+use crate::{MtPositionBaseData, MtPositionSide, TestEntity};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum MtPositionPendingStateType {
@@ -6,10 +8,78 @@ pub enum MtPositionPendingStateType {
     BuyLimit = 1,
     SellStop = 2,
     SellLimit = 3,
+    BuyStopLimit = 4,
+    SellStopLimit = 5,
+    BuyMarketIfTouched = 6,
+    SellMarketIfTouched = 7,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum MtPendingOrderKind {
+    Regular,
+    StopLimit { limit_price: f64 },
+    MarketIfTouched,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MtPendingBracket {
+    pub sl_distance: Option<f64>,
+    pub tp_distance: Option<f64>,
+}
+
+impl MtPendingBracket {
+    pub fn apply_to(&self, base_data: &mut MtPositionBaseData, fill_price: f64) {
+        if let Some(sl_distance) = self.sl_distance {
+            base_data.sl_price = Some(match base_data.side {
+                MtPositionSide::Buy => fill_price - sl_distance,
+                MtPositionSide::Sell => fill_price + sl_distance,
+            });
+        }
+
+        if let Some(tp_distance) = self.tp_distance {
+            base_data.tp_price = Some(match base_data.side {
+                MtPositionSide::Buy => fill_price + tp_distance,
+                MtPositionSide::Sell => fill_price - tp_distance,
+            });
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MtPositionPendingState {
     pub desire_price: f64,
     pub position_type: MtPositionPendingStateType,
+    pub limit_price: Option<f64>,
+    pub is_triggered: bool,
+    pub oco_group_id: Option<String>,
+    pub bracket: Option<MtPendingBracket>,
+}
+
+impl MtPositionPendingState {
+    pub fn get_order_kind(&self) -> MtPendingOrderKind {
+        match self.position_type {
+            MtPositionPendingStateType::BuyStopLimit
+            | MtPositionPendingStateType::SellStopLimit => MtPendingOrderKind::StopLimit {
+                limit_price: self.limit_price.unwrap_or(self.desire_price),
+            },
+            MtPositionPendingStateType::BuyMarketIfTouched
+            | MtPositionPendingStateType::SellMarketIfTouched => {
+                MtPendingOrderKind::MarketIfTouched
+            }
+            _ => MtPendingOrderKind::Regular,
+        }
+    }
+}
+
+impl TestEntity for MtPositionPendingState {
+    fn generate_test_entity() -> Self {
+        Self {
+            desire_price: 20.0,
+            position_type: MtPositionPendingStateType::BuyLimit,
+            limit_price: None,
+            is_triggered: false,
+            oco_group_id: None,
+            bracket: None,
+        }
+    }
 }
@@ -0,0 +1,31 @@
+use serde::{Deserialize, Serialize};
+
+use crate::MtPositionBaseData;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MtPositionProtection {
+    pub tp_profit: Option<f64>,
+    pub tp_price: Option<f64>,
+    pub sl_profit: Option<f64>,
+    pub sl_price: Option<f64>,
+}
+
+impl MtPositionProtection {
+    pub fn apply_to(&self, base_data: &mut MtPositionBaseData) {
+        base_data.tp_profit = self.tp_profit;
+        base_data.tp_price = self.tp_price;
+        base_data.sl_profit = self.sl_profit;
+        base_data.sl_price = self.sl_price;
+    }
+}
+
+impl From<&MtPositionBaseData> for MtPositionProtection {
+    fn from(base_data: &MtPositionBaseData) -> Self {
+        Self {
+            tp_profit: base_data.tp_profit,
+            tp_price: base_data.tp_price,
+            sl_profit: base_data.sl_profit,
+            sl_price: base_data.sl_price,
+        }
+    }
+}
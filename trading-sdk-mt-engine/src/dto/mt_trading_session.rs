@@ -0,0 +1,37 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MtTradingSessionWindow {
+    pub weekday: u32,
+    pub open_minute: u32,
+    pub close_minute: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MtTradingSessionBreak {
+    pub start_minute: u32,
+    pub end_minute: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MtTradingHoliday {
+    pub year: i32,
+    pub month: u32,
+    pub day: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MtTradingSchedule {
+    pub timezone: String,
+    pub windows: Vec<MtTradingSessionWindow>,
+    pub daily_breaks: Vec<MtTradingSessionBreak>,
+    pub holidays: Vec<MtTradingHoliday>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MtSessionTick {
+    pub asset_pair: String,
+    pub is_open: bool,
+    pub is_reopened: bool,
+    pub gap: Option<f64>,
+}
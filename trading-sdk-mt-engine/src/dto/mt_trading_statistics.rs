@@ -0,0 +1,39 @@
+use std::collections::HashMap;
+
+use rust_extensions::date_time::DateTimeAsMicroseconds;
+use serde::{Deserialize, Serialize};
+
+use crate::MtPositionCloseReason;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MtTradingStatistics {
+    pub total_positions: usize,
+    pub winning_positions: usize,
+    pub losing_positions: usize,
+    pub win_rate: f64,
+    pub gross_profit: f64,
+    pub gross_loss: f64,
+    pub net_profit: f64,
+    pub average_win: f64,
+    pub average_loss: f64,
+    pub profit_factor: Option<f64>,
+    pub max_drawdown: f64,
+    pub average_holding_microseconds: i64,
+    pub total_swaps: f64,
+    pub total_fees: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MtEquityPoint {
+    pub date: DateTimeAsMicroseconds,
+    pub balance: f64,
+    pub equity: f64,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MtTradingStatisticsReport {
+    pub totals: MtTradingStatistics,
+    pub by_instrument: HashMap<String, MtTradingStatistics>,
+    pub by_close_reason: HashMap<MtPositionCloseReason, MtTradingStatistics>,
+    pub equity_curve: Option<Vec<MtEquityPoint>>,
+}
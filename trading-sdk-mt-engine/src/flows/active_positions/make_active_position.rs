@@ -4,10 +4,13 @@ use rust_extensions::date_time::DateTimeAsMicroseconds;
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    get_base_collateral_open_price, get_close_price, get_open_price,
-    get_quote_collateral_close_price, sanitize_sl_tp, update_position_pl, MtBidAskCache,
-    MtEngineError, MtPosition, MtPositionActiveState, MtPositionActiveStateOpenData,
-    MtPositionBaseData, MtPositionPendingState, MtPositionSide,
+    calculate_position_fees, get_base_collateral_open_price, get_close_price, get_open_price,
+    get_quote_collateral_close_price, normalize_optional_price, sanitize_sl_tp, update_position_pl,
+    validate_instrument_asset_pair, validate_invest_amount, validate_price_age,
+    validate_price_deviation, validate_price_on_tick, validate_trading_session, MtBidAsk,
+    MtBidAskCache, MtEngineError, MtFeeModelsCache, MtInstrument, MtInstrumentsCache, MtPosition,
+    MtPositionActiveState, MtPositionActiveStateOpenData, MtPositionBaseData,
+    MtPositionPendingState, MtPositionSide, TestEntity,
 };
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -29,19 +32,112 @@ pub struct MtPositionOpenCommand {
     pub tp_price: Option<f64>,
     pub sl_profit: Option<f64>,
     pub sl_price: Option<f64>,
+    pub expected_price: Option<f64>,
+    pub max_deviation: Option<f64>,
     pub margin_call_percent: Option<f64>,
     pub topping_up_percent: Option<f64>,
     pub metadata: Option<HashMap<String, String>>,
+    pub group_id: Option<String>,
+}
+
+impl TestEntity for MtPositionOpenCommand {
+    fn generate_test_entity() -> Self {
+        Self {
+            id: "id".to_string(),
+            trader_id: "trader_id".to_string(),
+            account_id: "account_id".to_string(),
+            side: MtPositionSide::Buy,
+            asset_pair: "BASEQUOTE".to_string(),
+            base: "base".to_string(),
+            quote: "quote".to_string(),
+            collateral: "quote".to_string(),
+            invest_amount: 100.0,
+            leverage: 10.0,
+            stop_out_percent: 30.0,
+            process_id: "process".to_string(),
+            pending_state: None,
+            tp_profit: None,
+            tp_price: None,
+            sl_profit: None,
+            sl_price: None,
+            expected_price: None,
+            max_deviation: None,
+            margin_call_percent: None,
+            topping_up_percent: None,
+            metadata: None,
+            group_id: None,
+        }
+    }
 }
 
 pub fn make_active_position(
     open_command: MtPositionOpenCommand,
     prices_cache: &MtBidAskCache,
+    instruments_cache: &MtInstrumentsCache,
+    fees_cache: &MtFeeModelsCache,
 ) -> Result<MtPosition<MtPositionActiveState>, MtEngineError> {
+    let instrument = instruments_cache
+        .get_by_id(&open_command.asset_pair)
+        .ok_or_else(|| MtEngineError::InstrumentNotFound {
+            asset_pair: open_command.asset_pair.clone(),
+        })?;
+
     let asset_price = prices_cache
         .get_by_id(&open_command.asset_pair)
-        .ok_or(MtEngineError::NoLiquidity)?;
+        .ok_or_else(|| MtEngineError::NoLiquidity {
+            asset_pair: open_command.asset_pair.clone(),
+        })?;
+
+    let validation_result = validate_instrument_asset_pair(
+        &instrument,
+        &asset_price,
+        &open_command.base,
+        &open_command.quote,
+    )
+    .and_then(|_| {
+        validate_invest_amount(
+            &instrument,
+            open_command.invest_amount,
+            open_command.leverage,
+        )
+    })
+    .and_then(|_| validate_price_on_tick(&instrument, open_command.tp_price))
+    .and_then(|_| validate_price_on_tick(&instrument, open_command.sl_price))
+    .and_then(|_| validate_trading_session(&instrument, DateTimeAsMicroseconds::now()));
+
+    if let Err(violation) = validation_result {
+        return Err(MtEngineError::validation_failed(
+            &open_command.id,
+            violation,
+        ));
+    }
+
+    validate_price_age(&instrument, &asset_price, DateTimeAsMicroseconds::now())?;
+
+    validate_price_deviation(
+        &instrument,
+        &asset_price,
+        &open_command.side,
+        open_command.expected_price,
+        open_command.max_deviation,
+    )?;
 
+    build_active_position(
+        open_command,
+        &instrument,
+        &asset_price,
+        prices_cache,
+        fees_cache,
+    )
+}
+
+pub fn build_active_position(
+    open_command: MtPositionOpenCommand,
+    instrument: &MtInstrument,
+    asset_price: &MtBidAsk,
+    prices_cache: &MtBidAskCache,
+    fees_cache: &MtFeeModelsCache,
+) -> Result<MtPosition<MtPositionActiveState>, MtEngineError> {
     let (base_collateral_open_price, base_collateral_open_bid_ask) =
         get_base_collateral_open_price(
             prices_cache,
@@ -59,8 +155,8 @@ pub fn make_active_position(
         )?;
 
     let open_data = MtPositionActiveStateOpenData {
-        asset_open_price: get_open_price(&asset_price, &open_command.side),
-        asset_open_bid_ask: asset_price.as_ref().clone(),
+        asset_open_price: get_open_price(asset_price, &open_command.side),
+        asset_open_bid_ask: asset_price.clone(),
         base_collateral_open_price,
         base_collateral_open_bid_ask,
 
@@ -71,14 +167,16 @@ pub fn make_active_position(
 
     let active_state = MtPositionActiveState {
         open_data,
-        asset_active_price: get_close_price(asset_price.as_ref(), &open_command.side),
-        asset_active_bid_ask: asset_price.as_ref().clone(),
+        asset_active_price: get_close_price(asset_price, &open_command.side),
+        asset_active_bid_ask: asset_price.clone(),
         quote_collateral_active_price: quote_collateral_close_price,
         quote_collateral_active_bid_ask: quote_collateral_close_bid_ask,
         profit: 0.0,
         swaps: crate::MtPositionSwaps::default(),
         topping_up: None,
-        is_margin_call_hit: false
+        is_margin_call_hit: false,
+        fees: crate::MtPositionFees::default(),
+        margin_call_notification: crate::MtMarginCallNotificationState::default(),
     };
 
     let mut base_data = MtPositionBaseData {
@@ -98,11 +196,12 @@ pub fn make_active_position(
         base: open_command.base,
         quote: open_command.quote,
         tp_profit: open_command.tp_profit,
-        tp_price: open_command.tp_price,
+        tp_price: normalize_optional_price(instrument, open_command.tp_price),
         sl_profit: open_command.sl_profit,
-        sl_price: open_command.sl_price,
+        sl_price: normalize_optional_price(instrument, open_command.sl_price),
         topping_up_percent: open_command.topping_up_percent,
         metadata: open_command.metadata,
+        group_id: open_command.group_id,
         margin_call_percent: open_command.margin_call_percent,
     };
 
@@ -113,7 +212,165 @@ pub fn make_active_position(
         base_data,
     };
 
-    update_position_pl(&mut position);
+    if let Some(fee_model) = fees_cache.get(
+        &position.base_data.asset_pair,
+        position.base_data.group_id.as_deref(),
+    ) {
+        position.state.fees = calculate_position_fees(&position, fee_model);
+    }
+
+    update_position_pl(&mut position)?;
 
     Ok(position)
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        make_active_position, MtBidAsk, MtBidAskCache, MtEngineError, MtFeeModelsCache,
+        MtInstrument, MtInstrumentsCache, MtPositionOpenCommand, MtValidationViolation, TestEntity,
+    };
+
+    fn create_caches() -> (MtBidAskCache, MtInstrumentsCache) {
+        let prices_cache = MtBidAskCache::from_iter(vec![MtBidAsk {
+            bid: 1.0588,
+            ask: 1.0688,
+            ..MtBidAsk::generate_test_entity()
+        }]);
+
+        let instruments_cache = MtInstrumentsCache::from_iter(vec![MtInstrument {
+            min_invest_amount: Some(100.0),
+            max_invest_amount: Some(10000.0),
+            allowed_leverages: Some(vec![10.0, 20.0]),
+            tick_size: Some(0.0001),
+            ..MtInstrument::generate_test_entity()
+        }]);
+
+        (prices_cache, instruments_cache)
+    }
+
+    #[test]
+    fn test_open_position() {
+        let (prices_cache, instruments_cache) = create_caches();
+
+        let position = make_active_position(
+            MtPositionOpenCommand::generate_test_entity(),
+            &prices_cache,
+            &instruments_cache,
+            &MtFeeModelsCache::new(),
+        )
+        .unwrap();
+
+        assert_eq!(position.state.open_data.asset_open_price, 1.0688);
+        assert_eq!(position.state.asset_active_price, 1.0588);
+    }
+
+    #[test]
+    fn test_open_position_validation() {
+        let (prices_cache, instruments_cache) = create_caches();
+
+        let mut command = MtPositionOpenCommand::generate_test_entity();
+        command.asset_pair = "GBPUSD".to_string();
+        let result = make_active_position(
+            command,
+            &prices_cache,
+            &instruments_cache,
+            &MtFeeModelsCache::new(),
+        );
+        assert!(matches!(
+            result,
+            Err(MtEngineError::InstrumentNotFound { .. })
+        ));
+
+        let mut command = MtPositionOpenCommand::generate_test_entity();
+        command.base = "GBP".to_string();
+        let result = make_active_position(
+            command,
+            &prices_cache,
+            &instruments_cache,
+            &MtFeeModelsCache::new(),
+        );
+        assert!(matches!(
+            result,
+            Err(MtEngineError::ValidationFailed {
+                violation: MtValidationViolation::AssetPairMismatch { .. },
+                ..
+            })
+        ));
+
+        let mut command = MtPositionOpenCommand::generate_test_entity();
+        command.invest_amount = 10.0;
+        let result = make_active_position(
+            command,
+            &prices_cache,
+            &instruments_cache,
+            &MtFeeModelsCache::new(),
+        );
+        assert!(matches!(
+            result,
+            Err(MtEngineError::ValidationFailed {
+                violation: MtValidationViolation::InvestAmountTooLow { .. },
+                ..
+            })
+        ));
+
+        let mut command = MtPositionOpenCommand::generate_test_entity();
+        command.leverage = 100.0;
+        let result = make_active_position(
+            command,
+            &prices_cache,
+            &instruments_cache,
+            &MtFeeModelsCache::new(),
+        );
+        assert!(matches!(
+            result,
+            Err(MtEngineError::ValidationFailed {
+                violation: MtValidationViolation::LeverageNotAllowed { .. },
+                ..
+            })
+        ));
+
+        let mut command = MtPositionOpenCommand::generate_test_entity();
+        command.sl_price = Some(1.00005);
+        let result = make_active_position(
+            command,
+            &prices_cache,
+            &instruments_cache,
+            &MtFeeModelsCache::new(),
+        );
+        assert!(matches!(
+            result,
+            Err(MtEngineError::ValidationFailed {
+                violation: MtValidationViolation::PriceNotOnTick { .. },
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_open_position_requote() {
+        let (prices_cache, instruments_cache) = create_caches();
+
+        let mut command = MtPositionOpenCommand::generate_test_entity();
+        command.expected_price = Some(1.0680);
+        command.max_deviation = Some(0.0010);
+        assert!(make_active_position(
+            command,
+            &prices_cache,
+            &instruments_cache,
+            &MtFeeModelsCache::new()
+        )
+        .is_ok());
+
+        let mut command = MtPositionOpenCommand::generate_test_entity();
+        command.expected_price = Some(1.0600);
+        command.max_deviation = Some(0.0010);
+        let result = make_active_position(
+            command,
+            &prices_cache,
+            &instruments_cache,
+            &MtFeeModelsCache::new(),
+        );
+        assert!(matches!(result, Err(MtEngineError::Requote { .. })));
+    }
+}
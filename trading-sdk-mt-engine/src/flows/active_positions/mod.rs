@@ -1,7 +1,11 @@
 mod make_active_position;
 mod update_active_rate;
 mod topping_up;
+mod position_fees;
+mod preview_position_open;
 
 pub use make_active_position::*;
 pub use update_active_rate::*;
-pub use topping_up::*;
\ No newline at end of file
+pub use topping_up::*;
+pub use position_fees::*;
+pub use preview_position_open::*;
\ No newline at end of file
@@ -0,0 +1,128 @@
+use crate::{
+    get_position_base_volume, MtFeeModel, MtPosition, MtPositionActiveState, MtPositionFees,
+};
+
+pub fn calculate_position_fees(
+    position: &MtPosition<MtPositionActiveState>,
+    fee_model: &MtFeeModel,
+) -> MtPositionFees {
+    let notional = position.base_data.invest_amount * position.base_data.leverage;
+
+    fee_model.calculate_position_fees(notional, get_position_base_volume(position))
+}
+
+// A percentage close fee is estimated from the open notional while the position is active.
+// On close it is re-priced from the close notional; the difference is applied on top of
+// close_fee so that fees added to it by netting are kept.
+pub fn settle_close_fee(position: &mut MtPosition<MtPositionActiveState>) {
+    let Some(percent) = position.state.fees.close_fee_percent else {
+        return;
+    };
+
+    let open_notional = position.base_data.invest_amount * position.base_data.leverage;
+    let close_notional = get_position_close_notional(position);
+    let adjustment = (close_notional - open_notional) * percent / 100.0;
+
+    position.state.fees.close_fee += adjustment;
+    position.state.profit -= adjustment;
+}
+
+fn get_position_close_notional(position: &MtPosition<MtPositionActiveState>) -> f64 {
+    let base_volume = get_position_base_volume(position);
+
+    if position.base_data.base == position.base_data.collateral {
+        return base_volume;
+    }
+
+    let quote_notional = base_volume * position.state.asset_active_price;
+
+    if position.base_data.quote == position.base_data.collateral {
+        return quote_notional;
+    }
+
+    let inverted_quote = match &position.state.quote_collateral_active_bid_ask {
+        Some(bid_ask) => bid_ask.quote != position.base_data.collateral,
+        None => false,
+    };
+
+    match inverted_quote {
+        true => quote_notional / position.state.quote_collateral_active_price,
+        false => quote_notional * position.state.quote_collateral_active_price,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        calculate_position_fees, convert_position_to_closed, update_position_pl,
+        MtFeeCalculationType, MtFeeChargeType, MtFeeModel, MtPosition, MtPositionCloseReason,
+        TestEntity,
+    };
+
+    #[test]
+    fn test_position_fees() {
+        let mut position = MtPosition::generate_test_entity();
+        position.base_data.collateral = "quote".to_string();
+        position.base_data.invest_amount = 100.0;
+        position.base_data.leverage = 10.0;
+        position.state.open_data.asset_open_price = 25.0;
+
+        let per_lot = MtFeeModel {
+            calculation_type: MtFeeCalculationType::PerLot {
+                contract_size: 10.0,
+                amount_per_lot: 2.0,
+            },
+            charge_type: MtFeeChargeType::Both,
+        };
+
+        let fees = calculate_position_fees(&position, &per_lot);
+        assert_eq!(fees.open_fee, 8.0);
+        assert_eq!(fees.close_fee, 8.0);
+
+        let notional_percent = MtFeeModel {
+            calculation_type: MtFeeCalculationType::NotionalPercent { percent: 0.1 },
+            charge_type: MtFeeChargeType::Close,
+        };
+
+        let fees = calculate_position_fees(&position, &notional_percent);
+        assert_eq!(fees.open_fee, 0.0);
+        assert_eq!(fees.close_fee, 1.0);
+
+        position.state.fees = calculate_position_fees(&position, &per_lot);
+        update_position_pl(&mut position).unwrap();
+
+        assert_eq!(position.state.profit, -16.0);
+    }
+
+    #[test]
+    fn test_close_fee_from_close_notional() {
+        let mut position = MtPosition::generate_test_entity();
+        position.base_data.collateral = "quote".to_string();
+        position.base_data.invest_amount = 100.0;
+        position.base_data.leverage = 10.0;
+        position.state.open_data.asset_open_price = 25.0;
+        position.state.asset_active_price = 30.0;
+
+        let fee_model = MtFeeModel {
+            calculation_type: MtFeeCalculationType::NotionalPercent { percent: 0.1 },
+            charge_type: MtFeeChargeType::Both,
+        };
+
+        position.state.fees = calculate_position_fees(&position, &fee_model);
+        update_position_pl(&mut position).unwrap();
+
+        assert_eq!(position.state.fees.close_fee, 1.0);
+        assert_eq!(format!("{:.2}", position.state.profit), "198.00");
+
+        let closed = convert_position_to_closed(
+            position,
+            MtPositionCloseReason::ClientCommand,
+            "process".to_string(),
+        );
+
+        let fees = &closed.state.active_state.fees;
+        assert_eq!(fees.open_fee, 1.0);
+        assert_eq!(format!("{:.2}", fees.close_fee), "1.20");
+        assert_eq!(format!("{:.2}", closed.state.active_state.profit), "197.80");
+    }
+}
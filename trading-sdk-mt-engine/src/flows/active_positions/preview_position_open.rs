@@ -0,0 +1,136 @@
+use crate::{
+    get_margin_call_price, get_position_base_volume, get_stop_out_price, make_active_position,
+    update_position_pl, MtBidAskCache, MtEngineError, MtFeeModelsCache, MtInstrumentsCache,
+    MtPosition, MtPositionActiveState, MtPositionFees, MtPositionOpenCommand,
+};
+
+#[derive(Debug, Clone)]
+pub struct MtPositionOpenPreview {
+    pub open_price: f64,
+    pub required_margin: f64,
+    pub notional: f64,
+    pub base_volume: f64,
+    pub pip_value: Option<f64>,
+    pub estimated_fees: MtPositionFees,
+    pub stop_out_price: Option<f64>,
+    pub margin_call_price: Option<f64>,
+    pub sl_profit: Option<f64>,
+    pub tp_profit: Option<f64>,
+}
+
+pub fn preview_position_open(
+    open_command: &MtPositionOpenCommand,
+    prices_cache: &MtBidAskCache,
+    instruments_cache: &MtInstrumentsCache,
+    fees_cache: &MtFeeModelsCache,
+) -> Result<MtPositionOpenPreview, MtEngineError> {
+    let position = make_active_position(
+        open_command.clone(),
+        prices_cache,
+        instruments_cache,
+        fees_cache,
+    )?;
+
+    let open_price = position.state.open_data.asset_open_price;
+
+    let tick_size = instruments_cache
+        .get_by_id(&position.base_data.asset_pair)
+        .and_then(|instrument| instrument.tick_size);
+
+    let pip_value = match tick_size {
+        Some(tick_size) => Some(
+            (get_profit_at_price(&position, open_price + tick_size)?
+                - get_profit_at_price(&position, open_price)?)
+            .abs(),
+        ),
+        None => None,
+    };
+
+    let sl_profit = match position.base_data.sl_price {
+        Some(sl_price) => Some(get_profit_at_price(&position, sl_price)?),
+        None => position.base_data.sl_profit,
+    };
+
+    let tp_profit = match position.base_data.tp_price {
+        Some(tp_price) => Some(get_profit_at_price(&position, tp_price)?),
+        None => position.base_data.tp_profit,
+    };
+
+    Ok(MtPositionOpenPreview {
+        open_price,
+        required_margin: position.base_data.invest_amount,
+        notional: position.base_data.invest_amount * position.base_data.leverage,
+        base_volume: get_position_base_volume(&position),
+        pip_value,
+        estimated_fees: position.state.fees.clone(),
+        stop_out_price: get_stop_out_price(&position),
+        margin_call_price: get_margin_call_price(&position),
+        sl_profit,
+        tp_profit,
+    })
+}
+
+pub fn get_profit_at_price(
+    position: &MtPosition<MtPositionActiveState>,
+    price: f64,
+) -> Result<f64, MtEngineError> {
+    let mut position = position.clone();
+    position.state.asset_active_price = price;
+    update_position_pl(&mut position)?;
+
+    Ok(position.state.profit)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        preview_position_open, MtBidAsk, MtBidAskCache, MtFeeCalculationType, MtFeeChargeType,
+        MtFeeModel, MtFeeModelsCache, MtInstrument, MtInstrumentsCache, MtPositionOpenCommand,
+        TestEntity,
+    };
+
+    #[test]
+    fn test_preview_position_open() {
+        let command = MtPositionOpenCommand {
+            stop_out_percent: 50.0,
+            tp_price: Some(1.25),
+            sl_profit: Some(-20.0),
+            ..MtPositionOpenCommand::generate_test_entity()
+        };
+
+        let prices_cache = MtBidAskCache::from_iter(vec![MtBidAsk {
+            bid: 1.0,
+            ask: 1.0,
+            ..MtBidAsk::generate_test_entity()
+        }]);
+
+        let mut instrument = MtInstrument::generate_test_entity();
+        instrument.tick_size = Some(0.0001);
+        let instruments_cache = MtInstrumentsCache::from_iter(vec![instrument]);
+
+        let mut fees_cache = MtFeeModelsCache::new();
+        fees_cache.handle_new(
+            "BASEQUOTE",
+            None,
+            MtFeeModel {
+                calculation_type: MtFeeCalculationType::Fixed { amount: 1.0 },
+                charge_type: MtFeeChargeType::Open,
+            },
+        );
+
+        let preview =
+            preview_position_open(&command, &prices_cache, &instruments_cache, &fees_cache)
+                .unwrap();
+
+        assert_eq!(preview.open_price, 1.0);
+        assert_eq!(preview.required_margin, 100.0);
+        assert_eq!(preview.notional, 1000.0);
+        assert_eq!(preview.base_volume, 1000.0);
+        assert_eq!(format!("{:.4}", preview.pip_value.unwrap()), "0.1000");
+        assert_eq!(preview.estimated_fees.open_fee, 1.0);
+        assert_eq!(format!("{:.4}", preview.stop_out_price.unwrap()), "0.9510");
+        assert!(preview.margin_call_price.is_none());
+        assert_eq!(preview.sl_profit, Some(-20.0));
+        assert_eq!(format!("{:.2}", preview.tp_profit.unwrap()), "249.00");
+    }
+}
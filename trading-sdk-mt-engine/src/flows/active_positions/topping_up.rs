@@ -1,30 +1,50 @@
-use crate::{MtPosition, MtPositionActiveState};
+use crate::{MtEngineError, MtPosition, MtPositionActiveState};
 
 pub fn apply_position_topping_up(
     topping_up_amount: f64,
     position: &mut MtPosition<MtPositionActiveState>,
-) {
+) -> Result<(), MtEngineError> {
+    validate_topping_up_amount(topping_up_amount, position)?;
+
     if let Some(topping_up) = &position.state.topping_up {
         position.state.topping_up = Some(topping_up + topping_up_amount);
     } else {
         position.state.topping_up = Some(topping_up_amount);
     }
+
+    Ok(())
 }
 
-pub fn return_topping_up(topping_up_amount: f64, position: &mut MtPosition<MtPositionActiveState>) {
-    if let Some(topping_up) = &position.state.topping_up {
-        if topping_up < &topping_up_amount {
-            position.state.topping_up = Some(0.0);
-            return;
-        }
+pub fn return_topping_up(
+    topping_up_amount: f64,
+    position: &mut MtPosition<MtPositionActiveState>,
+) -> Result<f64, MtEngineError> {
+    validate_topping_up_amount(topping_up_amount, position)?;
 
-        position.state.topping_up = Some(topping_up - topping_up_amount);
-    } else {
-        panic!(
-            "Topping up amount is not set, cant return for position: {}",
-            position.base_data.id
-        )
+    let Some(topping_up) = position.state.topping_up else {
+        return Err(MtEngineError::ToppingUpNotSet {
+            position_id: position.base_data.id.clone(),
+        });
+    };
+
+    let returned_amount = topping_up_amount.min(topping_up);
+    position.state.topping_up = Some(topping_up - returned_amount);
+
+    Ok(returned_amount)
+}
+
+fn validate_topping_up_amount(
+    topping_up_amount: f64,
+    position: &MtPosition<MtPositionActiveState>,
+) -> Result<(), MtEngineError> {
+    if !topping_up_amount.is_finite() || topping_up_amount <= 0.0 {
+        return Err(MtEngineError::InvalidToppingUpAmount {
+            position_id: position.base_data.id.clone(),
+            amount: topping_up_amount,
+        });
     }
+
+    Ok(())
 }
 
 #[cfg(test)]
@@ -37,7 +57,7 @@ mod test {
         let mut position = MtPosition::generate_test_entity();
 
         position.state.topping_up = Some(10.0);
-        apply_position_topping_up(10.0, &mut position);
+        apply_position_topping_up(10.0, &mut position).unwrap();
 
         assert_eq!(position.state.topping_up, Some(20.0));
     }
@@ -47,33 +67,51 @@ mod test {
         let mut position = MtPosition::generate_test_entity();
 
         position.state.topping_up = None;
-        apply_position_topping_up(10.0, &mut position);
+        apply_position_topping_up(10.0, &mut position).unwrap();
 
         assert_eq!(position.state.topping_up, Some(10.0));
     }
 
+    #[test]
+    fn test_apply_position_topping_up_invalid_amount() {
+        let mut position = MtPosition::generate_test_entity();
+
+        position.state.topping_up = None;
+        let result = apply_position_topping_up(-10.0, &mut position);
+
+        assert!(matches!(
+            result,
+            Err(MtEngineError::InvalidToppingUpAmount { .. })
+        ));
+        assert_eq!(position.state.topping_up, None);
+    }
+
     #[test]
     fn test_return_topping_up() {
         let mut position = MtPosition::generate_test_entity();
         position.state.topping_up = Some(10.0);
-        return_topping_up(5.0, &mut position);
+        let returned_amount = return_topping_up(5.0, &mut position).unwrap();
 
+        assert_eq!(returned_amount, 5.0);
         assert_eq!(position.state.topping_up, Some(5.0));
     }
 
     #[test]
-    #[should_panic]
-    fn test_return_topping_up_panic_no_funds() {
+    fn test_return_topping_up_no_funds() {
         let mut position = MtPosition::generate_test_entity();
         position.state.topping_up = Some(3.0);
-        return_topping_up(5.0, &mut position);
+        let returned_amount = return_topping_up(5.0, &mut position).unwrap();
+
+        assert_eq!(returned_amount, 3.0);
+        assert_eq!(position.state.topping_up, Some(0.0));
     }
 
     #[test]
-    #[should_panic]
-    fn test_return_topping_up_panic_no_topping_up() {
+    fn test_return_topping_up_no_topping_up() {
         let mut position = MtPosition::generate_test_entity();
         position.state.topping_up = None;
-        return_topping_up(5.0, &mut position);
+        let result = return_topping_up(5.0, &mut position);
+
+        assert!(matches!(result, Err(MtEngineError::ToppingUpNotSet { .. })));
     }
 }
@@ -83,6 +83,7 @@ mod test {
             margin_call_percent: None,
             topping_up_percent: None,
             metadata: None,
+            group_id: None,
         };
 
         let open_data: MtPositionActiveStateOpenData = MtPositionActiveStateOpenData {
@@ -104,7 +105,9 @@ mod test {
             profit: 0.0,
             swaps: MtPositionSwaps::default(),
             topping_up: None,
-            is_margin_call_hit: false
+            is_margin_call_hit: false,
+            fees: crate::MtPositionFees::default(),
+            margin_call_notification: crate::MtMarginCallNotificationState::default(),
         };
 
         let mut position = MtPosition {
@@ -0,0 +1,5 @@
+mod mt_balance_provider;
+mod process_position_topping_up;
+
+pub use mt_balance_provider::*;
+pub use process_position_topping_up::*;
@@ -0,0 +1,22 @@
+use rust_extensions::date_time::DateTimeAsMicroseconds;
+use serde::{Deserialize, Serialize};
+
+pub trait MtBalanceProvider {
+    fn reserve(&mut self, account_id: &str, amount: f64) -> bool;
+    fn release(&mut self, account_id: &str, amount: f64);
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum MtBalanceMovementType {
+    ToppingUpReserved = 0,
+    ToppingUpReleased = 1,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MtBalanceMovement {
+    pub position_id: String,
+    pub account_id: String,
+    pub movement_type: MtBalanceMovementType,
+    pub amount: f64,
+    pub date: DateTimeAsMicroseconds,
+}
@@ -0,0 +1,353 @@
+use rust_extensions::date_time::DateTimeAsMicroseconds;
+
+use crate::{
+    apply_position_topping_up, calculate_position_topping_up, calculate_topping_up_count,
+    can_return_topping_up_funds_within, get_close_reason, return_topping_up,
+    update_margin_call_state, MtBalanceMovement, MtBalanceMovementType, MtBalanceProvider,
+    MtEngineError, MtInstrument, MtMarginCallEvent, MtMarginCallSettings, MtPosition,
+    MtPositionActiveState, MtPositionCloseReason,
+};
+
+pub struct MtToppingUpResult {
+    pub movements: Vec<MtBalanceMovement>,
+    pub is_reservation_failed: bool,
+    pub close_reason: Option<MtPositionCloseReason>,
+    pub margin_call_event: Option<MtMarginCallEvent>,
+}
+
+pub fn process_position_topping_up(
+    position: &mut MtPosition<MtPositionActiveState>,
+    instrument: &MtInstrument,
+    margin_call_settings: &MtMarginCallSettings,
+    balance_provider: &mut impl MtBalanceProvider,
+) -> Result<MtToppingUpResult, MtEngineError> {
+    let mut movements = vec![];
+    let mut is_reservation_failed = false;
+
+    if let (Some(topping_up_amount), Some(target_margin_used_percent)) = (
+        calculate_position_topping_up(&position.base_data)?,
+        get_target_margin_used_percent(position, margin_call_settings),
+    ) {
+        let topping_up_count =
+            calculate_topping_up_count(position, topping_up_amount, target_margin_used_percent)?;
+
+        if topping_up_count > 0 {
+            let amount = topping_up_amount * topping_up_count as f64;
+
+            if balance_provider.reserve(&position.base_data.account_id, amount) {
+                if let Err(err) = apply_position_topping_up(amount, position) {
+                    balance_provider.release(&position.base_data.account_id, amount);
+                    return Err(err);
+                }
+
+                movements.push(create_movement(
+                    position,
+                    MtBalanceMovementType::ToppingUpReserved,
+                    amount,
+                ));
+            } else {
+                is_reservation_failed = true;
+            }
+        } else if can_return_topping_up_funds_within(position, target_margin_used_percent)? {
+            let amount = return_topping_up(topping_up_amount, position)?;
+            balance_provider.release(&position.base_data.account_id, amount);
+
+            movements.push(create_movement(
+                position,
+                MtBalanceMovementType::ToppingUpReleased,
+                amount,
+            ));
+        }
+    }
+
+    let margin_call_event = update_margin_call_state(
+        position,
+        margin_call_settings,
+        DateTimeAsMicroseconds::now(),
+    )?;
+
+    let close_reason = get_close_reason(position, instrument)?;
+
+    Ok(MtToppingUpResult {
+        movements,
+        is_reservation_failed,
+        close_reason,
+        margin_call_event,
+    })
+}
+
+// Topping up has to take the position out of the margin call state: below the margin call
+// level, or below the recovery level once the margin call has been entered.
+fn get_target_margin_used_percent(
+    position: &MtPosition<MtPositionActiveState>,
+    margin_call_settings: &MtMarginCallSettings,
+) -> Option<f64> {
+    let margin_call_percent = position.base_data.margin_call_percent?;
+
+    match position.state.is_margin_call_hit {
+        true => Some(margin_call_settings.get_recovery_percent(margin_call_percent)),
+        false => Some(margin_call_percent),
+    }
+}
+
+fn create_movement(
+    position: &MtPosition<MtPositionActiveState>,
+    movement_type: MtBalanceMovementType,
+    amount: f64,
+) -> MtBalanceMovement {
+    MtBalanceMovement {
+        position_id: position.base_data.id.clone(),
+        account_id: position.base_data.account_id.clone(),
+        movement_type,
+        amount,
+        date: DateTimeAsMicroseconds::now(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        get_close_reason, process_position_topping_up, MtBalanceMovementType, MtBalanceProvider,
+        MtInstrument, MtInstrumentStatus, MtMarginCallEvent, MtMarginCallRecoveryRule,
+        MtMarginCallSettings, MtPosition, MtPositionActiveState, MtPositionBaseData,
+        MtPositionCloseReason, TestEntity,
+    };
+
+    struct TestBalanceProvider {
+        balance: f64,
+    }
+
+    impl MtBalanceProvider for TestBalanceProvider {
+        fn reserve(&mut self, _account_id: &str, amount: f64) -> bool {
+            if self.balance < amount {
+                return false;
+            }
+
+            self.balance -= amount;
+            true
+        }
+
+        fn release(&mut self, _account_id: &str, amount: f64) {
+            self.balance += amount;
+        }
+    }
+
+    fn create_settings() -> MtMarginCallSettings {
+        MtMarginCallSettings {
+            recovery_rule: MtMarginCallRecoveryRule::Band { band_percent: 10.0 },
+            renotify_interval_microseconds: None,
+        }
+    }
+
+    #[test]
+    fn test_reserve_and_release() {
+        let mut position = MtPosition {
+            base_data: MtPositionBaseData {
+                stop_out_percent: 90.0,
+                margin_call_percent: Some(50.0),
+                topping_up_percent: Some(50.0),
+                ..MtPositionBaseData::generate_test_entity()
+            },
+            state: MtPositionActiveState::generate_test_entity(),
+        };
+        let instrument = MtInstrument::generate_test_entity();
+        let settings = create_settings();
+        let mut provider = TestBalanceProvider { balance: 1000.0 };
+
+        position.state.profit = -60.0;
+        let result =
+            process_position_topping_up(&mut position, &instrument, &settings, &mut provider)
+                .unwrap();
+
+        assert_eq!(result.movements.len(), 1);
+        assert!(matches!(
+            result.movements[0].movement_type,
+            MtBalanceMovementType::ToppingUpReserved
+        ));
+        assert_eq!(result.movements[0].amount, 50.0);
+        assert_eq!(position.state.topping_up, Some(50.0));
+        assert_eq!(provider.balance, 950.0);
+        assert!(!position.state.is_margin_call_hit);
+        assert!(result.close_reason.is_none());
+
+        position.state.profit = -10.0;
+        let result =
+            process_position_topping_up(&mut position, &instrument, &settings, &mut provider)
+                .unwrap();
+
+        assert_eq!(result.movements.len(), 1);
+        assert!(matches!(
+            result.movements[0].movement_type,
+            MtBalanceMovementType::ToppingUpReleased
+        ));
+        assert_eq!(position.state.topping_up, Some(0.0));
+        assert_eq!(provider.balance, 1000.0);
+    }
+
+    #[test]
+    fn test_failed_reservation_at_stop_out_level() {
+        let mut position = MtPosition {
+            base_data: MtPositionBaseData {
+                stop_out_percent: 90.0,
+                margin_call_percent: Some(50.0),
+                topping_up_percent: Some(50.0),
+                ..MtPositionBaseData::generate_test_entity()
+            },
+            state: MtPositionActiveState::generate_test_entity(),
+        };
+        let instrument = MtInstrument::generate_test_entity();
+        let settings = create_settings();
+        let mut provider = TestBalanceProvider { balance: 0.0 };
+
+        position.state.profit = -95.0;
+        let result =
+            process_position_topping_up(&mut position, &instrument, &settings, &mut provider)
+                .unwrap();
+
+        assert!(result.movements.is_empty());
+        assert!(result.is_reservation_failed);
+        assert!(position.state.is_margin_call_hit);
+        assert!(matches!(
+            result.close_reason,
+            Some(MtPositionCloseReason::StopOut)
+        ));
+    }
+
+    #[test]
+    fn test_failed_reservation_keeps_close_reason() {
+        let mut position = MtPosition {
+            base_data: MtPositionBaseData {
+                stop_out_percent: 90.0,
+                margin_call_percent: Some(50.0),
+                topping_up_percent: Some(50.0),
+                ..MtPositionBaseData::generate_test_entity()
+            },
+            state: MtPositionActiveState::generate_test_entity(),
+        };
+        let instrument = MtInstrument::generate_test_entity();
+        let settings = create_settings();
+        let mut provider = TestBalanceProvider { balance: 0.0 };
+
+        position.state.profit = -70.0;
+        let result =
+            process_position_topping_up(&mut position, &instrument, &settings, &mut provider)
+                .unwrap();
+
+        assert!(result.movements.is_empty());
+        assert!(result.is_reservation_failed);
+        assert!(position.state.is_margin_call_hit);
+        assert!(result.close_reason.is_none());
+        assert!(get_close_reason(&position, &instrument).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_topping_up_in_one_step() {
+        let mut position = MtPosition {
+            base_data: MtPositionBaseData {
+                stop_out_percent: 90.0,
+                margin_call_percent: Some(50.0),
+                topping_up_percent: Some(50.0),
+                ..MtPositionBaseData::generate_test_entity()
+            },
+            state: MtPositionActiveState::generate_test_entity(),
+        };
+        let instrument = MtInstrument::generate_test_entity();
+        let settings = create_settings();
+        let mut provider = TestBalanceProvider { balance: 1000.0 };
+
+        position.state.profit = -80.0;
+        let result =
+            process_position_topping_up(&mut position, &instrument, &settings, &mut provider)
+                .unwrap();
+
+        assert_eq!(result.movements.len(), 1);
+        assert_eq!(result.movements[0].amount, 100.0);
+        assert_eq!(position.state.topping_up, Some(100.0));
+        assert_eq!(provider.balance, 900.0);
+        assert!(!position.state.is_margin_call_hit);
+        assert!(result.close_reason.is_none());
+    }
+
+    #[test]
+    fn test_topping_up_leaves_margin_call_band() {
+        let mut position = MtPosition {
+            base_data: MtPositionBaseData {
+                stop_out_percent: 90.0,
+                margin_call_percent: Some(50.0),
+                topping_up_percent: Some(50.0),
+                ..MtPositionBaseData::generate_test_entity()
+            },
+            state: MtPositionActiveState::generate_test_entity(),
+        };
+        let instrument = MtInstrument::generate_test_entity();
+        let settings = create_settings();
+        let mut provider = TestBalanceProvider { balance: 1000.0 };
+
+        position.state.is_margin_call_hit = true;
+        position.state.profit = -45.0;
+        let result =
+            process_position_topping_up(&mut position, &instrument, &settings, &mut provider)
+                .unwrap();
+
+        assert_eq!(result.movements.len(), 1);
+        assert_eq!(position.state.topping_up, Some(50.0));
+        assert!(!position.state.is_margin_call_hit);
+    }
+
+    #[test]
+    fn test_halted_instrument_skips_close() {
+        let mut position = MtPosition {
+            base_data: MtPositionBaseData {
+                stop_out_percent: 90.0,
+                margin_call_percent: Some(50.0),
+                topping_up_percent: Some(50.0),
+                ..MtPositionBaseData::generate_test_entity()
+            },
+            state: MtPositionActiveState::generate_test_entity(),
+        };
+        let mut instrument = MtInstrument::generate_test_entity();
+        instrument.status = MtInstrumentStatus::Halted;
+        let settings = create_settings();
+        let mut provider = TestBalanceProvider { balance: 0.0 };
+
+        position.state.profit = -95.0;
+        let result =
+            process_position_topping_up(&mut position, &instrument, &settings, &mut provider)
+                .unwrap();
+
+        assert!(result.close_reason.is_none());
+    }
+
+    #[test]
+    fn test_margin_call_keeps_hysteresis() {
+        let mut position = MtPosition {
+            base_data: MtPositionBaseData {
+                stop_out_percent: 90.0,
+                margin_call_percent: Some(50.0),
+                topping_up_percent: Some(50.0),
+                ..MtPositionBaseData::generate_test_entity()
+            },
+            state: MtPositionActiveState::generate_test_entity(),
+        };
+        position.base_data.topping_up_percent = None;
+        let instrument = MtInstrument::generate_test_entity();
+        let settings = create_settings();
+        let mut provider = TestBalanceProvider { balance: 0.0 };
+
+        position.state.profit = -50.0;
+        let result =
+            process_position_topping_up(&mut position, &instrument, &settings, &mut provider)
+                .unwrap();
+        assert!(matches!(
+            result.margin_call_event,
+            Some(MtMarginCallEvent::MarginCallEntered { .. })
+        ));
+
+        position.state.profit = -45.0;
+        let result =
+            process_position_topping_up(&mut position, &instrument, &settings, &mut provider)
+                .unwrap();
+        assert!(result.margin_call_event.is_none());
+        assert!(position.state.is_margin_call_hit);
+    }
+}
@@ -1,26 +1,54 @@
-use crate::{get_position_total_invest, MtPosition, MtPositionActiveState, MtPositionCloseReason};
+use crate::{
+    get_position_total_invest, is_instrument_session_open, to_mt_amount, MtEngineError,
+    MtInstrument, MtInstrumentStatus, MtNumber, MtPosition, MtPositionActiveState,
+    MtPositionCloseReason,
+};
 
 pub fn get_close_reason(
     position: &MtPosition<MtPositionActiveState>,
-) -> Option<MtPositionCloseReason> {
-    if is_so_triggered(position) {
-        return Some(MtPositionCloseReason::StopOut);
+    instrument: &MtInstrument,
+) -> Result<Option<MtPositionCloseReason>, MtEngineError> {
+    if let MtInstrumentStatus::Delisting { .. } = instrument.status {
+        return Ok(Some(MtPositionCloseReason::ForceClose));
+    }
+
+    if !is_position_close_allowed(position, instrument) {
+        return Ok(None);
+    }
+
+    if is_so_triggered(position)? {
+        return Ok(Some(MtPositionCloseReason::StopOut));
     }
 
     if is_sl_triggered(position) {
-        return Some(MtPositionCloseReason::StopLoss);
+        return Ok(Some(MtPositionCloseReason::StopLoss));
     }
 
     if is_tp_triggered(position) {
-        return Some(MtPositionCloseReason::TakeProfit);
+        return Ok(Some(MtPositionCloseReason::TakeProfit));
     }
 
-    return None;
+    return Ok(None);
 }
 
-fn is_so_triggered(position: &MtPosition<MtPositionActiveState>) -> bool {
-    return 100.0 - calculate_position_margin_percent(position)
-        >= position.base_data.stop_out_percent;
+fn is_position_close_allowed(
+    position: &MtPosition<MtPositionActiveState>,
+    instrument: &MtInstrument,
+) -> bool {
+    match instrument.status {
+        MtInstrumentStatus::Halted => false,
+        MtInstrumentStatus::Enabled
+        | MtInstrumentStatus::CloseOnly
+        | MtInstrumentStatus::Delisting { .. } => {
+            is_instrument_session_open(instrument, position.state.asset_active_bid_ask.date)
+        }
+    }
+}
+
+fn is_so_triggered(position: &MtPosition<MtPositionActiveState>) -> Result<bool, MtEngineError> {
+    return Ok(
+        100.0 - calculate_position_margin_percent(position)? >= position.base_data.stop_out_percent
+    );
 }
 
 fn is_sl_triggered(position: &MtPosition<MtPositionActiveState>) -> bool {
@@ -53,9 +81,16 @@ fn is_tp_triggered(position: &MtPosition<MtPositionActiveState>) -> bool {
     return false;
 }
 
-fn calculate_position_margin_percent(position: &MtPosition<MtPositionActiveState>) -> f64 {
-    let margin = position.state.profit + get_position_total_invest(position);
-    return margin / get_position_total_invest(position) * 100.0;
+fn calculate_position_margin_percent(
+    position: &MtPosition<MtPositionActiveState>,
+) -> Result<f64, MtEngineError> {
+    let total_invest = to_mt_amount(get_position_total_invest(position)?)?;
+    let margin = to_mt_amount(position.state.profit)? + total_invest;
+
+    margin
+        .try_div(total_invest)?
+        .try_mul(to_mt_amount(100.0)?)?
+        .to_f64()
 }
 
 #[cfg(test)]
@@ -63,9 +98,9 @@ mod tests {
     use rust_extensions::date_time::DateTimeAsMicroseconds;
 
     use crate::{
-        get_close_price, get_open_price, update_position_pl, MtBidAsk, MtPosition,
+        get_close_price, get_open_price, update_position_pl, MtBidAsk, MtInstrument, MtPosition,
         MtPositionActiveState, MtPositionActiveStateOpenData, MtPositionBaseData,
-        MtPositionCloseReason, MtPositionSwaps,
+        MtPositionCloseReason, MtPositionSwaps, TestEntity,
     };
     #[test]
     fn test_tp_close_ok() {
@@ -100,6 +135,7 @@ mod tests {
             sl_price: None,
             topping_up_percent: None,
             metadata: None,
+            group_id: None,
             margin_call_percent: None,
         };
 
@@ -132,6 +168,8 @@ mod tests {
             swaps: MtPositionSwaps::default(),
             topping_up: None,
             is_margin_call_hit: false,
+            fees: crate::MtPositionFees::default(),
+            margin_call_notification: crate::MtMarginCallNotificationState::default(),
         };
 
         let mut position = MtPosition {
@@ -139,8 +177,10 @@ mod tests {
             base_data,
         };
 
-        update_position_pl(&mut position);
-        let cr = super::get_close_reason(&position).unwrap();
+        update_position_pl(&mut position).unwrap();
+        let cr = super::get_close_reason(&position, &MtInstrument::generate_test_entity())
+            .unwrap()
+            .unwrap();
 
         assert_eq!(format!("{:.2}", position.state.profit), 18.71.to_string());
         assert_eq!(matches!(cr, MtPositionCloseReason::TakeProfit), true);
@@ -179,6 +219,7 @@ mod tests {
             sl_price: None,
             topping_up_percent: None,
             metadata: None,
+            group_id: None,
             margin_call_percent: None,
         };
 
@@ -211,6 +252,8 @@ mod tests {
             swaps: MtPositionSwaps::default(),
             topping_up: None,
             is_margin_call_hit: false,
+            fees: crate::MtPositionFees::default(),
+            margin_call_notification: crate::MtMarginCallNotificationState::default(),
         };
 
         let mut position = MtPosition {
@@ -218,8 +261,8 @@ mod tests {
             base_data,
         };
 
-        update_position_pl(&mut position);
-        let cr = super::get_close_reason(&position);
+        update_position_pl(&mut position).unwrap();
+        let cr = super::get_close_reason(&position, &MtInstrument::generate_test_entity()).unwrap();
 
         assert_eq!(matches!(cr, None), true);
     }
@@ -257,6 +300,7 @@ mod tests {
             sl_price: None,
             topping_up_percent: None,
             metadata: None,
+            group_id: None,
             margin_call_percent: None,
         };
 
@@ -289,6 +333,8 @@ mod tests {
             swaps: MtPositionSwaps::default(),
             topping_up: None,
             is_margin_call_hit: false,
+            fees: crate::MtPositionFees::default(),
+            margin_call_notification: crate::MtMarginCallNotificationState::default(),
         };
 
         let mut position = MtPosition {
@@ -296,8 +342,10 @@ mod tests {
             base_data,
         };
 
-        update_position_pl(&mut position);
-        let cr = super::get_close_reason(&position).unwrap();
+        update_position_pl(&mut position).unwrap();
+        let cr = super::get_close_reason(&position, &MtInstrument::generate_test_entity())
+            .unwrap()
+            .unwrap();
 
         assert_eq!(format!("{:.2}", position.state.profit), 18.71.to_string());
         assert_eq!(matches!(cr, MtPositionCloseReason::TakeProfit), true);
@@ -336,6 +384,7 @@ mod tests {
             sl_price: Some(1.0697),
             topping_up_percent: None,
             metadata: None,
+            group_id: None,
             margin_call_percent: None,
         };
 
@@ -368,14 +417,18 @@ mod tests {
             swaps: MtPositionSwaps::default(),
             topping_up: None,
             is_margin_call_hit: false,
+            fees: crate::MtPositionFees::default(),
+            margin_call_notification: crate::MtMarginCallNotificationState::default(),
         };
         let mut position = MtPosition {
             state: active_state,
             base_data,
         };
 
-        update_position_pl(&mut position);
-        let cr = super::get_close_reason(&position).unwrap();
+        update_position_pl(&mut position).unwrap();
+        let cr = super::get_close_reason(&position, &MtInstrument::generate_test_entity())
+            .unwrap()
+            .unwrap();
 
         assert_eq!(
             format!("{:.2}", position.state.profit),
@@ -417,6 +470,7 @@ mod tests {
             sl_price: None,
             topping_up_percent: None,
             metadata: None,
+            group_id: None,
             margin_call_percent: None,
         };
 
@@ -449,6 +503,8 @@ mod tests {
             swaps: MtPositionSwaps::default(),
             topping_up: None,
             is_margin_call_hit: false,
+            fees: crate::MtPositionFees::default(),
+            margin_call_notification: crate::MtMarginCallNotificationState::default(),
         };
 
         let mut position = MtPosition {
@@ -456,8 +512,10 @@ mod tests {
             base_data,
         };
 
-        update_position_pl(&mut position);
-        let cr = super::get_close_reason(&position).unwrap();
+        update_position_pl(&mut position).unwrap();
+        let cr = super::get_close_reason(&position, &MtInstrument::generate_test_entity())
+            .unwrap()
+            .unwrap();
         assert_eq!(
             format!("{:.2}", position.state.profit),
             (-18.71).to_string()
@@ -497,6 +555,7 @@ mod tests {
             sl_profit: None,
             sl_price: None,
             metadata: None,
+            group_id: None,
             margin_call_percent: Some(20.0),
             topping_up_percent: Some(40.0),
         };
@@ -530,6 +589,8 @@ mod tests {
             swaps: MtPositionSwaps::default(),
             topping_up: Some(120.0),
             is_margin_call_hit: false,
+            fees: crate::MtPositionFees::default(),
+            margin_call_notification: crate::MtMarginCallNotificationState::default(),
         };
 
         let mut position = MtPosition {
@@ -537,8 +598,8 @@ mod tests {
             base_data,
         };
 
-        update_position_pl(&mut position);
-        let cr = super::get_close_reason(&position);
+        update_position_pl(&mut position).unwrap();
+        let cr = super::get_close_reason(&position, &MtInstrument::generate_test_entity()).unwrap();
         assert_eq!(
             format!("{:.2}", position.state.profit),
             (-18.71).to_string()
@@ -579,6 +640,7 @@ mod tests {
             sl_price: None,
             topping_up_percent: None,
             metadata: None,
+            group_id: None,
             margin_call_percent: None,
         };
 
@@ -611,6 +673,8 @@ mod tests {
             swaps: MtPositionSwaps::default(),
             topping_up: None,
             is_margin_call_hit: false,
+            fees: crate::MtPositionFees::default(),
+            margin_call_notification: crate::MtMarginCallNotificationState::default(),
         };
 
         let mut position = MtPosition {
@@ -618,14 +682,16 @@ mod tests {
             base_data,
         };
 
-        update_position_pl(&mut position);
+        update_position_pl(&mut position).unwrap();
 
         position.base_data.invest_amount = 1.0;
         position.base_data.topping_up_percent = Some(100.0);
         position.base_data.margin_call_percent = Some(40.0);
         position.state.topping_up = Some(99.0);
 
-        let cr = super::get_close_reason(&position).unwrap();
+        let cr = super::get_close_reason(&position, &MtInstrument::generate_test_entity())
+            .unwrap()
+            .unwrap();
         assert_eq!(
             format!("{:.2}", position.state.profit),
             (-18.71).to_string()
@@ -1,34 +1,48 @@
-use crate::{MtPosition, MtPositionActiveState, MtPositionSide};
+use crate::{
+    to_mt_amount, MtEngineError, MtNumber, MtPosition, MtPositionActiveState, MtPositionSide,
+};
 
-pub fn update_unrealized_pl(position: &mut MtPosition<MtPositionActiveState>) {
+pub fn update_unrealized_pl(
+    position: &mut MtPosition<MtPositionActiveState>,
+) -> Result<(), MtEngineError> {
     let is_inverted_instrument = position.base_data.quote != position.base_data.collateral;
 
     let side_coefficient = match position.base_data.side {
-        MtPositionSide::Buy => 1.0,
-        MtPositionSide::Sell => -1.0,
+        MtPositionSide::Buy => to_mt_amount(1.0)?,
+        MtPositionSide::Sell => to_mt_amount(-1.0)?,
     };
 
-    let volume = position.base_data.invest_amount * position.base_data.leverage;
+    let asset_open_price = to_mt_amount(position.state.open_data.asset_open_price)?;
+    let asset_active_price = to_mt_amount(position.state.asset_active_price)?;
+
+    let volume = to_mt_amount(position.base_data.invest_amount)?
+        .try_mul(to_mt_amount(position.base_data.leverage)?)?;
 
     let investment_volume = match is_inverted_instrument {
         true => volume,
-        false => volume / position.state.open_data.asset_open_price,
+        false => volume.try_div(asset_open_price)?,
     };
 
-    let price_change =
-        position.state.asset_active_price - position.state.open_data.asset_open_price;
+    let price_change = asset_active_price - asset_open_price;
 
-    let instrument_profit = investment_volume * price_change;
+    let instrument_profit = investment_volume.try_mul(price_change)?;
 
     let collateral_profit = match is_inverted_instrument {
-        true => instrument_profit / position.state.asset_active_price,
+        true => instrument_profit.try_div(asset_active_price)?,
         false => instrument_profit,
     };
 
-    position.state.profit = collateral_profit * side_coefficient + position.state.swaps.total;
+    position.state.profit = (collateral_profit.try_mul(side_coefficient)?
+        + to_mt_amount(position.state.swaps.total)?
+        - to_mt_amount(position.state.fees.get_total())?)
+    .to_f64()?;
+
+    Ok(())
 }
 
-pub fn update_collateral_unrealized_pl(position: &mut MtPosition<MtPositionActiveState>) {
+pub fn update_collateral_unrealized_pl(
+    position: &mut MtPosition<MtPositionActiveState>,
+) -> Result<(), MtEngineError> {
     let inverted_base = position
         .state
         .open_data
@@ -37,17 +51,21 @@ pub fn update_collateral_unrealized_pl(position: &mut MtPosition<MtPositionActiv
         .unwrap()
         .quote
         != position.base_data.collateral;
-    let volume = position.base_data.invest_amount * position.base_data.leverage;
+    let volume = to_mt_amount(position.base_data.invest_amount)?
+        .try_mul(to_mt_amount(position.base_data.leverage)?)?;
+
+    let base_collateral_open_price =
+        to_mt_amount(position.state.open_data.base_collateral_open_price)?;
 
     let investment_volume = match inverted_base {
-        true => volume * position.state.open_data.base_collateral_open_price,
-        false => volume / position.state.open_data.base_collateral_open_price,
+        true => volume.try_mul(base_collateral_open_price)?,
+        false => volume.try_div(base_collateral_open_price)?,
     };
 
-    let price_change =
-        position.state.asset_active_price - position.state.open_data.asset_open_price;
+    let price_change = to_mt_amount(position.state.asset_active_price)?
+        - to_mt_amount(position.state.open_data.asset_open_price)?;
 
-    let base_profit = investment_volume * price_change;
+    let base_profit = investment_volume.try_mul(price_change)?;
 
     let inverted_quote = position
         .state
@@ -57,30 +75,60 @@ pub fn update_collateral_unrealized_pl(position: &mut MtPosition<MtPositionActiv
         .quote
         != position.base_data.collateral;
 
+    let quote_collateral_active_price = to_mt_amount(position.state.quote_collateral_active_price)?;
+
     let collateral_currency_profit = match inverted_quote {
-        true => base_profit / position.state.quote_collateral_active_price,
-        false => base_profit * position.state.quote_collateral_active_price,
+        true => base_profit.try_div(quote_collateral_active_price)?,
+        false => base_profit.try_mul(quote_collateral_active_price)?,
     };
 
     let side_coefficient = match position.base_data.side {
-        MtPositionSide::Buy => 1.0,
-        MtPositionSide::Sell => -1.0,
+        MtPositionSide::Buy => to_mt_amount(1.0)?,
+        MtPositionSide::Sell => to_mt_amount(-1.0)?,
     };
 
-    position.state.profit =
-        collateral_currency_profit * side_coefficient + position.state.swaps.total;
+    position.state.profit = (collateral_currency_profit.try_mul(side_coefficient)?
+        + to_mt_amount(position.state.swaps.total)?
+        - to_mt_amount(position.state.fees.get_total())?)
+    .to_f64()?;
+
+    Ok(())
 }
 
-pub fn update_position_pl(position: &mut MtPosition<MtPositionActiveState>) {
+pub fn update_position_pl(
+    position: &mut MtPosition<MtPositionActiveState>,
+) -> Result<(), MtEngineError> {
     if position.base_data.base == position.base_data.collateral
         || position.base_data.quote == position.base_data.collateral
     {
-        update_unrealized_pl(position);
+        update_unrealized_pl(position)
     } else {
         update_collateral_unrealized_pl(position)
     }
 }
 
+pub fn get_position_base_volume(position: &MtPosition<MtPositionActiveState>) -> f64 {
+    let volume = position.base_data.invest_amount * position.base_data.leverage;
+
+    if position.base_data.base == position.base_data.collateral {
+        return volume;
+    }
+
+    if position.base_data.quote == position.base_data.collateral {
+        return volume / position.state.open_data.asset_open_price;
+    }
+
+    let inverted_base = match &position.state.open_data.base_collateral_open_bid_ask {
+        Some(bid_ask) => bid_ask.quote != position.base_data.collateral,
+        None => false,
+    };
+
+    match inverted_base {
+        true => volume * position.state.open_data.base_collateral_open_price,
+        false => volume / position.state.open_data.base_collateral_open_price,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use rust_extensions::date_time::DateTimeAsMicroseconds;
@@ -123,6 +171,7 @@ mod tests {
             sl_price: None,
             topping_up_percent: None,
             metadata: None,
+            group_id: None,
             margin_call_percent: None,
         };
 
@@ -154,7 +203,9 @@ mod tests {
             profit: 0.0,
             swaps: MtPositionSwaps::default(),
             topping_up: None,
-            is_margin_call_hit: false
+            is_margin_call_hit: false,
+            fees: crate::MtPositionFees::default(),
+            margin_call_notification: crate::MtMarginCallNotificationState::default(),
         };
 
         let mut position = MtPosition {
@@ -162,7 +213,7 @@ mod tests {
             base_data,
         };
 
-        update_position_pl(&mut position);
+        update_position_pl(&mut position).unwrap();
 
         assert_eq!(format!("{:.2}", position.state.profit), 18.71.to_string());
     }
@@ -200,6 +251,7 @@ mod tests {
             sl_price: None,
             topping_up_percent: None,
             metadata: None,
+            group_id: None,
             margin_call_percent: None,
         };
 
@@ -231,7 +283,9 @@ mod tests {
             profit: 0.0,
             swaps: MtPositionSwaps::default(),
             topping_up: None,
-            is_margin_call_hit: false
+            is_margin_call_hit: false,
+            fees: crate::MtPositionFees::default(),
+            margin_call_notification: crate::MtMarginCallNotificationState::default(),
         };
 
         let mut position = MtPosition {
@@ -239,7 +293,7 @@ mod tests {
             base_data,
         };
 
-        update_position_pl(&mut position);
+        update_position_pl(&mut position).unwrap();
 
         assert_eq!(
             format!("{:.4}", position.state.profit),
@@ -289,6 +343,7 @@ mod tests {
             sl_price: None,
             topping_up_percent: None,
             metadata: None,
+            group_id: None,
             margin_call_percent: None,
         };
 
@@ -335,7 +390,9 @@ mod tests {
             profit: 0.0,
             swaps: MtPositionSwaps::default(),
             topping_up: None,
-            is_margin_call_hit: false
+            is_margin_call_hit: false,
+            fees: crate::MtPositionFees::default(),
+            margin_call_notification: crate::MtMarginCallNotificationState::default(),
         };
 
         let mut position = MtPosition {
@@ -343,11 +400,34 @@ mod tests {
             base_data,
         };
 
-        update_position_pl(&mut position);
+        update_position_pl(&mut position).unwrap();
 
         assert_eq!(
             format!("{:.4}", position.state.profit),
             (-240.2305).to_string()
         );
     }
+
+    #[cfg(feature = "decimal")]
+    #[test]
+    fn test_decimal_invalid_amounts_are_errors() {
+        use crate::{get_close_reason, MtEngineError, MtInstrument, TestEntity};
+
+        let mut position: MtPosition<MtPositionActiveState> = MtPosition::generate_test_entity();
+        position.base_data.collateral = position.base_data.quote.clone();
+        position.base_data.invest_amount = 1e20;
+        position.base_data.leverage = 1e20;
+
+        assert!(matches!(
+            update_position_pl(&mut position),
+            Err(MtEngineError::AmountOverflow)
+        ));
+
+        position.state.profit = f64::NAN;
+
+        assert!(matches!(
+            get_close_reason(&position, &MtInstrument::generate_test_entity()),
+            Err(MtEngineError::InvalidAmount { .. })
+        ));
+    }
 }
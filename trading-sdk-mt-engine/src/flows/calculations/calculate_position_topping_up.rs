@@ -1,33 +1,91 @@
-use crate::{get_position_total_invest, MtPosition, MtPositionActiveState, MtPositionBaseData};
+use crate::{
+    get_position_total_invest, to_mt_amount, MtEngineError, MtNumber, MtPosition,
+    MtPositionActiveState, MtPositionBaseData,
+};
+
+pub fn calculate_position_topping_up(
+    position: &MtPositionBaseData,
+) -> Result<Option<f64>, MtEngineError> {
+    let Some(topping_up_percent) = position.topping_up_percent else {
+        return Ok(None);
+    };
+
+    let topping_up = to_mt_amount(topping_up_percent)?
+        .try_mul(to_mt_amount(position.invest_amount)?)?
+        .try_div(to_mt_amount(100.0)?)?;
 
-pub fn calculate_position_topping_up(position: &MtPositionBaseData) -> Option<f64> {
-    Some(position.topping_up_percent? * position.invest_amount / 100.0)
+    Ok(Some(topping_up.to_f64()?))
 }
 
-pub fn can_return_topping_up_funds(position: &MtPosition<MtPositionActiveState>) -> bool {
+// Number of topping up steps needed to bring the used margin below the target percent.
+// Returns zero when the position is already below it or when no amount can reach it.
+pub fn calculate_topping_up_count(
+    position: &MtPosition<MtPositionActiveState>,
+    topping_up_amount: f64,
+    target_margin_used_percent: f64,
+) -> Result<u32, MtEngineError> {
+    let loss = -position.state.profit;
+
+    if loss <= 0.0 || target_margin_used_percent <= 0.0 || topping_up_amount <= 0.0 {
+        return Ok(0);
+    }
+
+    let required_invest = loss * 100.0 / target_margin_used_percent;
+    let missing_invest = required_invest - get_position_total_invest(position)?;
+
+    if !missing_invest.is_finite() || missing_invest < 0.0 {
+        return Ok(0);
+    }
+
+    Ok((missing_invest / topping_up_amount).floor() as u32 + 1)
+}
+
+pub fn can_return_topping_up_funds(
+    position: &MtPosition<MtPositionActiveState>,
+) -> Result<bool, MtEngineError> {
     let Some(margin_call) = position.base_data.margin_call_percent else {
-        return false;
+        return Ok(false);
     };
 
+    can_return_topping_up_funds_within(position, margin_call)
+}
+
+pub fn can_return_topping_up_funds_within(
+    position: &MtPosition<MtPositionActiveState>,
+    target_margin_used_percent: f64,
+) -> Result<bool, MtEngineError> {
     let Some(topping_up_sum) = position.state.topping_up else {
-        return false;
+        return Ok(false);
     };
 
     if topping_up_sum < 0.01 {
-        return false;
+        return Ok(false);
     }
 
-    let Some(topping_up_amount) = calculate_position_topping_up(&position.base_data) else {
-        return false;
+    let Some(topping_up_amount) = calculate_position_topping_up(&position.base_data)? else {
+        return Ok(false);
     };
 
+    let margin_used_percent =
+        calculate_margin_used_without_topping_up(position, topping_up_amount)?;
+
+    Ok(margin_used_percent < target_margin_used_percent)
+}
+
+fn calculate_margin_used_without_topping_up(
+    position: &MtPosition<MtPositionActiveState>,
+    topping_up_amount: f64,
+) -> Result<f64, MtEngineError> {
+    let total_invest = to_mt_amount(get_position_total_invest(position)?)?;
+    let topping_up_amount = to_mt_amount(topping_up_amount)?;
+
     let free_margin_without_last_topping_up =
-        get_position_total_invest(position) + position.state.profit - topping_up_amount;
+        total_invest + to_mt_amount(position.state.profit)? - topping_up_amount;
     let free_margin_without_last_topping_up_percent = free_margin_without_last_topping_up
-        / (get_position_total_invest(position) - topping_up_amount)
-        * 100.0;
+        .try_div(total_invest - topping_up_amount)?
+        .try_mul(to_mt_amount(100.0)?)?;
 
-    return 100.0 - free_margin_without_last_topping_up_percent < margin_call;
+    (to_mt_amount(100.0)? - free_margin_without_last_topping_up_percent).to_f64()
 }
 
 #[cfg(test)]
@@ -43,7 +101,9 @@ mod test {
         position.base_data.topping_up_percent = Some(50.0);
         position.base_data.margin_call_percent = Some(40.0);
 
-        let topping_up_amount = calculate_position_topping_up(&position.base_data).unwrap();
+        let topping_up_amount = calculate_position_topping_up(&position.base_data)
+            .unwrap()
+            .unwrap();
 
         assert_eq!(topping_up_amount, 500.0);
     }
@@ -54,7 +114,7 @@ mod test {
 
         position.base_data.topping_up_percent = None;
 
-        let topping_up_amount = calculate_position_topping_up(&position.base_data);
+        let topping_up_amount = calculate_position_topping_up(&position.base_data).unwrap();
 
         assert_eq!(matches!(topping_up_amount, None), true);
     }
@@ -68,8 +128,37 @@ mod test {
         position.base_data.margin_call_percent = Some(40.0);
         position.state.topping_up = Some(100.0);
 
-        let topping_up_amount = can_return_topping_up_funds(&position);
+        let topping_up_amount = can_return_topping_up_funds(&position).unwrap();
 
         assert_eq!(topping_up_amount, true);
     }
+
+    #[test]
+    fn test_topping_up_count() {
+        let mut position = MtPosition::generate_test_entity();
+
+        position.base_data.invest_amount = 100.0;
+        position.state.topping_up = None;
+
+        position.state.profit = -40.0;
+        assert_eq!(
+            calculate_topping_up_count(&position, 50.0, 50.0).unwrap(),
+            0
+        );
+
+        position.state.profit = -50.0;
+        assert_eq!(
+            calculate_topping_up_count(&position, 50.0, 50.0).unwrap(),
+            1
+        );
+
+        position.state.profit = -140.0;
+        assert_eq!(
+            calculate_topping_up_count(&position, 50.0, 50.0).unwrap(),
+            4
+        );
+
+        position.state.profit = -140.0;
+        assert_eq!(calculate_topping_up_count(&position, 50.0, 0.0).unwrap(), 0);
+    }
 }
@@ -0,0 +1,155 @@
+use crate::{get_position_total_invest, MtPosition, MtPositionActiveState, MtPositionSide};
+
+pub fn get_stop_out_price(position: &MtPosition<MtPositionActiveState>) -> Option<f64> {
+    get_price_for_margin_used_percent(position, position.base_data.stop_out_percent)
+}
+
+pub fn get_margin_call_price(position: &MtPosition<MtPositionActiveState>) -> Option<f64> {
+    get_price_for_margin_used_percent(position, position.base_data.margin_call_percent?)
+}
+
+pub fn get_price_for_margin_used_percent(
+    position: &MtPosition<MtPositionActiveState>,
+    margin_used_percent: f64,
+) -> Option<f64> {
+    let profit = -get_position_total_invest(position).ok()? * margin_used_percent / 100.0;
+    get_price_for_profit(position, profit)
+}
+
+pub fn get_price_for_profit(
+    position: &MtPosition<MtPositionActiveState>,
+    profit: f64,
+) -> Option<f64> {
+    let side_coefficient = match position.base_data.side {
+        MtPositionSide::Buy => 1.0,
+        MtPositionSide::Sell => -1.0,
+    };
+
+    let collateral_profit =
+        (profit - position.state.swaps.total + position.state.fees.get_total()) * side_coefficient;
+
+    let volume = position.base_data.invest_amount * position.base_data.leverage;
+    let open_price = position.state.open_data.asset_open_price;
+
+    let price = if position.base_data.quote == position.base_data.collateral {
+        open_price + collateral_profit * open_price / volume
+    } else if position.base_data.base == position.base_data.collateral {
+        if volume - collateral_profit <= 0.0 {
+            return None;
+        }
+
+        volume * open_price / (volume - collateral_profit)
+    } else {
+        let inverted_base = position
+            .state
+            .open_data
+            .base_collateral_open_bid_ask
+            .as_ref()?
+            .quote
+            != position.base_data.collateral;
+
+        let investment_volume = match inverted_base {
+            true => volume * position.state.open_data.base_collateral_open_price,
+            false => volume / position.state.open_data.base_collateral_open_price,
+        };
+
+        let inverted_quote = position
+            .state
+            .quote_collateral_active_bid_ask
+            .as_ref()?
+            .quote
+            != position.base_data.collateral;
+
+        // The quote/collateral rate is assumed to stay at its current level.
+        let base_profit = match inverted_quote {
+            true => collateral_profit * position.state.quote_collateral_active_price,
+            false => collateral_profit / position.state.quote_collateral_active_price,
+        };
+
+        open_price + base_profit / investment_volume
+    };
+
+    if !price.is_finite() || price <= 0.0 {
+        return None;
+    }
+
+    Some(price)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        get_close_reason, get_margin_call_price, get_stop_out_price, update_position_pl,
+        MtInstrument, MtPosition, MtPositionActiveState, MtPositionCloseReason, MtPositionSide,
+        TestEntity,
+    };
+
+    fn assert_stop_out_price(side: MtPositionSide, collateral: &str) {
+        let mut position: MtPosition<MtPositionActiveState> = MtPosition::generate_test_entity();
+        position.base_data.side = side;
+        position.base_data.collateral = collateral.to_string();
+        position.base_data.leverage = 10.0;
+        position.base_data.stop_out_percent = 50.0;
+        position.base_data.margin_call_percent = Some(30.0);
+        position.state.open_data.asset_open_price = 20.0;
+        position.state.open_data.base_collateral_open_price = 2.0;
+        position.state.quote_collateral_active_price = 4.0;
+        position.state.topping_up = Some(20.0);
+        position.state.swaps.add_swap(-5.0);
+
+        let stop_out_price = get_stop_out_price(&position).unwrap();
+
+        position.state.asset_active_price = stop_out_price;
+        update_position_pl(&mut position).unwrap();
+
+        assert_eq!(format!("{:.6}", position.state.profit), "-60.000000");
+
+        position.state.asset_active_price = match position.base_data.side {
+            MtPositionSide::Buy => stop_out_price - 0.0001,
+            MtPositionSide::Sell => stop_out_price + 0.0001,
+        };
+        update_position_pl(&mut position).unwrap();
+
+        assert!(matches!(
+            get_close_reason(&position, &MtInstrument::generate_test_entity()).unwrap(),
+            Some(MtPositionCloseReason::StopOut)
+        ));
+
+        let margin_call_price = get_margin_call_price(&position).unwrap();
+
+        position.state.asset_active_price = margin_call_price;
+        update_position_pl(&mut position).unwrap();
+
+        assert_eq!(format!("{:.6}", position.state.profit), "-36.000000");
+    }
+
+    #[test]
+    fn test_same_currency_instrument() {
+        for side in [MtPositionSide::Buy, MtPositionSide::Sell] {
+            assert_stop_out_price(side, "quote");
+        }
+    }
+
+    #[test]
+    fn test_inverted_instrument() {
+        for side in [MtPositionSide::Buy, MtPositionSide::Sell] {
+            assert_stop_out_price(side, "base");
+        }
+    }
+
+    #[test]
+    fn test_cross_instrument() {
+        for side in [MtPositionSide::Buy, MtPositionSide::Sell] {
+            assert_stop_out_price(side, "collateral");
+        }
+    }
+
+    #[test]
+    fn test_no_margin_call() {
+        let mut position: MtPosition<MtPositionActiveState> = MtPosition::generate_test_entity();
+        position.base_data.margin_call_percent = None;
+
+        assert!(get_margin_call_price(&position).is_none());
+        assert!(get_stop_out_price(&position).is_some());
+    }
+}
@@ -0,0 +1,163 @@
+use rust_extensions::date_time::DateTimeAsMicroseconds;
+
+use crate::{
+    get_position_margin_used_percent, MtEngineError, MtMarginCallEvent, MtMarginCallSettings,
+    MtPosition, MtPositionActiveState,
+};
+
+pub fn update_margin_call_state(
+    position: &mut MtPosition<MtPositionActiveState>,
+    settings: &MtMarginCallSettings,
+    now: DateTimeAsMicroseconds,
+) -> Result<Option<MtMarginCallEvent>, MtEngineError> {
+    let margin_used_percent = get_position_margin_used_percent(position)?;
+
+    position.state.is_margin_call_hit = match position.base_data.margin_call_percent {
+        Some(margin_call_percent) => match position.state.is_margin_call_hit {
+            true => margin_used_percent >= settings.get_recovery_percent(margin_call_percent),
+            false => margin_used_percent >= margin_call_percent,
+        },
+        None => false,
+    };
+
+    let notification = &mut position.state.margin_call_notification;
+
+    if notification.is_notified_hit == position.state.is_margin_call_hit {
+        return Ok(None);
+    }
+
+    if let (Some(last_date), Some(interval)) = (
+        &notification.last_notification_date,
+        settings.renotify_interval_microseconds,
+    ) {
+        if now.unix_microseconds - last_date.unix_microseconds < interval {
+            return Ok(None);
+        }
+    }
+
+    notification.is_notified_hit = position.state.is_margin_call_hit;
+    notification.last_notification_date = Some(now);
+
+    let position_id = position.base_data.id.clone();
+    let account_id = position.base_data.account_id.clone();
+
+    let event = match position.state.is_margin_call_hit {
+        true => MtMarginCallEvent::MarginCallEntered {
+            position_id,
+            account_id,
+            margin_used_percent,
+            date: now,
+        },
+        false => MtMarginCallEvent::MarginCallRecovered {
+            position_id,
+            account_id,
+            margin_used_percent,
+            date: now,
+        },
+    };
+
+    Ok(Some(event))
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_extensions::date_time::DateTimeAsMicroseconds;
+
+    use crate::{
+        update_margin_call_state, MtMarginCallEvent, MtMarginCallRecoveryRule,
+        MtMarginCallSettings, MtPosition, TestEntity,
+    };
+
+    #[test]
+    fn test_hysteresis_band() {
+        let mut position = MtPosition::generate_test_entity();
+        position.base_data.invest_amount = 100.0;
+        position.base_data.margin_call_percent = Some(50.0);
+
+        let settings = MtMarginCallSettings {
+            recovery_rule: MtMarginCallRecoveryRule::Band { band_percent: 10.0 },
+            renotify_interval_microseconds: None,
+        };
+        let now = DateTimeAsMicroseconds::new(1_000_000);
+
+        position.state.profit = -50.0;
+        let event = update_margin_call_state(&mut position, &settings, now).unwrap();
+        assert!(matches!(
+            event,
+            Some(MtMarginCallEvent::MarginCallEntered { .. })
+        ));
+        assert!(position.state.is_margin_call_hit);
+
+        position.state.profit = -45.0;
+        assert!(update_margin_call_state(&mut position, &settings, now)
+            .unwrap()
+            .is_none());
+        assert!(position.state.is_margin_call_hit);
+
+        position.state.profit = -39.0;
+        let event = update_margin_call_state(&mut position, &settings, now).unwrap();
+        assert!(matches!(
+            event,
+            Some(MtMarginCallEvent::MarginCallRecovered { .. })
+        ));
+        assert!(!position.state.is_margin_call_hit);
+
+        position.state.profit = -45.0;
+        assert!(update_margin_call_state(&mut position, &settings, now)
+            .unwrap()
+            .is_none());
+        assert!(!position.state.is_margin_call_hit);
+    }
+
+    #[test]
+    fn test_renotify_interval() {
+        let mut position = MtPosition::generate_test_entity();
+        position.base_data.invest_amount = 100.0;
+        position.base_data.margin_call_percent = Some(50.0);
+
+        let settings = MtMarginCallSettings {
+            recovery_rule: MtMarginCallRecoveryRule::Threshold {
+                recovery_percent: 50.0,
+            },
+            renotify_interval_microseconds: Some(1_000_000),
+        };
+
+        position.state.profit = -60.0;
+        assert!(
+            update_margin_call_state(&mut position, &settings, DateTimeAsMicroseconds::new(0))
+                .unwrap()
+                .is_some()
+        );
+
+        position.state.profit = -40.0;
+        assert!(update_margin_call_state(
+            &mut position,
+            &settings,
+            DateTimeAsMicroseconds::new(500_000)
+        )
+        .unwrap()
+        .is_none());
+        assert!(!position.state.is_margin_call_hit);
+
+        position.state.profit = -60.0;
+        assert!(update_margin_call_state(
+            &mut position,
+            &settings,
+            DateTimeAsMicroseconds::new(700_000)
+        )
+        .unwrap()
+        .is_none());
+
+        position.state.profit = -40.0;
+        let event = update_margin_call_state(
+            &mut position,
+            &settings,
+            DateTimeAsMicroseconds::new(1_500_000),
+        )
+        .unwrap();
+        assert!(matches!(
+            event,
+            Some(MtMarginCallEvent::MarginCallRecovered { .. })
+        ));
+    }
+}
@@ -1,9 +1,15 @@
-mod calculate_pl;
 mod background_close;
-mod is_margin_call_hit;
+mod calculate_pl;
 mod calculate_position_topping_up;
+mod is_margin_call_hit;
+mod liquidation_price;
+mod margin_call_state;
+mod mt_amount;
 
-pub use calculate_pl::*;
 pub use background_close::*;
+pub use calculate_pl::*;
+pub use calculate_position_topping_up::*;
 pub use is_margin_call_hit::*;
-pub use calculate_position_topping_up::*;
\ No newline at end of file
+pub use liquidation_price::*;
+pub use margin_call_state::*;
+pub use mt_amount::*;
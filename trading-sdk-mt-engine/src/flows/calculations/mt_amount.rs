@@ -0,0 +1,174 @@
+use std::ops::{Add, Neg, Sub};
+
+use crate::{MtAmountRounding, MtEngineError, MtRoundingMode};
+
+// Positions keep their money fields as f64. With the "decimal" feature each calculation
+// converts its inputs to decimals and its result back to f64, so settlement rounding is the
+// only step that is exact. Everywhere else the feature provides checked arithmetic:
+// overflow and values that cannot be represented come back as errors.
+#[cfg(not(feature = "decimal"))]
+pub type MtAmount = f64;
+
+#[cfg(feature = "decimal")]
+pub type MtAmount = rust_decimal::Decimal;
+
+pub trait MtNumber:
+    Copy + PartialOrd + Add<Output = Self> + Sub<Output = Self> + Neg<Output = Self>
+{
+    fn try_from_f64(value: f64) -> Result<Self, MtEngineError>;
+    fn to_f64(self) -> Result<f64, MtEngineError>;
+    fn try_mul(self, rhs: Self) -> Result<Self, MtEngineError>;
+    fn try_div(self, rhs: Self) -> Result<Self, MtEngineError>;
+    fn round_with(self, rounding: &MtAmountRounding) -> Self;
+}
+
+impl MtNumber for f64 {
+    fn try_from_f64(value: f64) -> Result<Self, MtEngineError> {
+        Ok(value)
+    }
+
+    fn to_f64(self) -> Result<f64, MtEngineError> {
+        Ok(self)
+    }
+
+    fn try_mul(self, rhs: Self) -> Result<Self, MtEngineError> {
+        Ok(self * rhs)
+    }
+
+    fn try_div(self, rhs: Self) -> Result<Self, MtEngineError> {
+        Ok(self / rhs)
+    }
+
+    fn round_with(self, rounding: &MtAmountRounding) -> Self {
+        let factor = 10f64.powi(rounding.decimals as i32);
+        let scaled = self * factor;
+
+        let rounded = match rounding.mode {
+            MtRoundingMode::HalfUp => scaled.round(),
+            MtRoundingMode::HalfEven => scaled.round_ties_even(),
+            MtRoundingMode::Down => scaled.trunc(),
+            MtRoundingMode::Up => match scaled >= 0.0 {
+                true => scaled.ceil(),
+                false => scaled.floor(),
+            },
+        };
+
+        rounded / factor
+    }
+}
+
+#[cfg(feature = "decimal")]
+impl MtNumber for rust_decimal::Decimal {
+    fn try_from_f64(value: f64) -> Result<Self, MtEngineError> {
+        rust_decimal::prelude::FromPrimitive::from_f64(value)
+            .ok_or(MtEngineError::InvalidAmount { value })
+    }
+
+    fn to_f64(self) -> Result<f64, MtEngineError> {
+        rust_decimal::prelude::ToPrimitive::to_f64(&self).ok_or(MtEngineError::AmountOverflow)
+    }
+
+    fn try_mul(self, rhs: Self) -> Result<Self, MtEngineError> {
+        self.checked_mul(rhs).ok_or(MtEngineError::AmountOverflow)
+    }
+
+    fn try_div(self, rhs: Self) -> Result<Self, MtEngineError> {
+        if rhs.is_zero() {
+            return Err(MtEngineError::AmountDivisionByZero);
+        }
+
+        self.checked_div(rhs).ok_or(MtEngineError::AmountOverflow)
+    }
+
+    fn round_with(self, rounding: &MtAmountRounding) -> Self {
+        let strategy = match rounding.mode {
+            MtRoundingMode::HalfUp => rust_decimal::RoundingStrategy::MidpointAwayFromZero,
+            MtRoundingMode::HalfEven => rust_decimal::RoundingStrategy::MidpointNearestEven,
+            MtRoundingMode::Down => rust_decimal::RoundingStrategy::ToZero,
+            MtRoundingMode::Up => rust_decimal::RoundingStrategy::AwayFromZero,
+        };
+
+        self.round_dp_with_strategy(rounding.decimals, strategy)
+    }
+}
+
+pub fn to_mt_amount(value: f64) -> Result<MtAmount, MtEngineError> {
+    MtAmount::try_from_f64(value)
+}
+
+pub fn round_mt_amount(value: f64, rounding: &MtAmountRounding) -> Result<f64, MtEngineError> {
+    to_mt_amount(value)?.round_with(rounding).to_f64()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{round_mt_amount, MtAmountRounding, MtRoundingMode};
+
+    fn rounding(mode: MtRoundingMode) -> MtAmountRounding {
+        MtAmountRounding { decimals: 2, mode }
+    }
+
+    #[test]
+    fn test_rounding_modes() {
+        assert_eq!(
+            round_mt_amount(0.125, &rounding(MtRoundingMode::HalfUp)).unwrap(),
+            0.13
+        );
+        assert_eq!(
+            round_mt_amount(0.125, &rounding(MtRoundingMode::HalfEven)).unwrap(),
+            0.12
+        );
+        assert_eq!(
+            round_mt_amount(0.129, &rounding(MtRoundingMode::Down)).unwrap(),
+            0.12
+        );
+        assert_eq!(
+            round_mt_amount(0.121, &rounding(MtRoundingMode::Up)).unwrap(),
+            0.13
+        );
+        assert_eq!(
+            round_mt_amount(-0.125, &rounding(MtRoundingMode::HalfUp)).unwrap(),
+            -0.13
+        );
+        assert_eq!(
+            round_mt_amount(-0.121, &rounding(MtRoundingMode::Up)).unwrap(),
+            -0.13
+        );
+        assert_eq!(
+            round_mt_amount(-0.129, &rounding(MtRoundingMode::Down)).unwrap(),
+            -0.12
+        );
+    }
+
+    #[cfg(feature = "decimal")]
+    #[test]
+    fn test_decimal_rounding_to_the_cent() {
+        assert_eq!(
+            round_mt_amount(1.005, &rounding(MtRoundingMode::HalfUp)).unwrap(),
+            1.01
+        );
+        assert_eq!(
+            round_mt_amount(2.675, &rounding(MtRoundingMode::HalfUp)).unwrap(),
+            2.68
+        );
+    }
+
+    #[cfg(feature = "decimal")]
+    #[test]
+    fn test_decimal_invalid_amounts() {
+        use crate::{to_mt_amount, MtEngineError, MtNumber};
+
+        assert!(matches!(
+            to_mt_amount(f64::NAN),
+            Err(MtEngineError::InvalidAmount { .. })
+        ));
+
+        let one = to_mt_amount(1.0).unwrap();
+        let zero = to_mt_amount(0.0).unwrap();
+
+        assert!(matches!(
+            one.try_div(zero),
+            Err(MtEngineError::AmountDivisionByZero)
+        ));
+    }
+}
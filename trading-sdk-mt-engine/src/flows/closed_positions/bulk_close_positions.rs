@@ -0,0 +1,140 @@
+use trading_sdk_core::EngineCacheQueryBuilder;
+
+use crate::{
+    convert_position_to_closed, get_any_price_by_tickers, update_active_position_rate,
+    update_position_pl, ActivePositionsCache, MtBidAsk, MtBidAskCache, MtEngineError, MtPosition,
+    MtPositionActiveState, MtPositionCloseReason, MtPositionClosedState,
+};
+
+pub struct MtBulkCloseResult {
+    pub closed_positions: Vec<MtPosition<MtPositionClosedState>>,
+    pub skipped_position_ids: Vec<String>,
+}
+
+pub fn bulk_close_positions(
+    active_cache: &mut ActivePositionsCache,
+    query: EngineCacheQueryBuilder,
+    close_reason: MtPositionCloseReason,
+    prices_cache: &MtBidAskCache,
+    process_id: String,
+) -> Result<MtBulkCloseResult, MtEngineError> {
+    let mut closed_positions = vec![];
+    let mut skipped_position_ids = vec![];
+
+    for position in active_cache.0.query_positions(query) {
+        let Some(bid_asks) = get_position_close_bid_asks(position, prices_cache) else {
+            skipped_position_ids.push(position.base_data.id.clone());
+            continue;
+        };
+
+        let mut position = position.clone();
+
+        for bid_ask in bid_asks {
+            update_active_position_rate(&mut position, &bid_ask);
+        }
+
+        update_position_pl(&mut position)?;
+        closed_positions.push(convert_position_to_closed(
+            position,
+            close_reason.clone(),
+            process_id.clone(),
+        ));
+    }
+
+    // Positions are only removed once every close has been calculated, so a failed
+    // calculation leaves the cache untouched.
+    for position in &closed_positions {
+        active_cache.0.remove_position(&position.base_data.id);
+    }
+
+    Ok(MtBulkCloseResult {
+        closed_positions,
+        skipped_position_ids,
+    })
+}
+
+fn get_position_close_bid_asks(
+    position: &MtPosition<MtPositionActiveState>,
+    prices_cache: &MtBidAskCache,
+) -> Option<Vec<MtBidAsk>> {
+    let asset_bid_ask = prices_cache.get_by_id(&position.base_data.asset_pair)?;
+    let mut result = vec![asset_bid_ask.as_ref().clone()];
+
+    if position.base_data.quote != position.base_data.collateral {
+        result.push(get_any_price_by_tickers(
+            prices_cache,
+            &position.base_data.collateral,
+            &position.base_data.quote,
+        )?);
+    }
+
+    Some(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_extensions::date_time::DateTimeAsMicroseconds;
+    use trading_sdk_core::EngineCacheQueryBuilder;
+
+    use crate::{
+        bulk_close_positions, ActivePositionsCache, MtBidAsk, MtBidAskCache, MtPosition,
+        MtPositionActiveState, MtPositionCloseReason, TestEntity,
+    };
+
+    #[test]
+    fn test_bulk_close_by_account() {
+        let mut cache = ActivePositionsCache::new();
+
+        for (id, account_id, asset_pair, base) in [
+            ("1", "account", "EURUSD", "EUR"),
+            ("2", "account", "GBPUSD", "GBP"),
+            ("3", "other", "EURUSD", "EUR"),
+        ] {
+            let mut position: MtPosition<MtPositionActiveState> =
+                MtPosition::generate_test_entity();
+            position.base_data.id = id.to_string();
+            position.base_data.account_id = account_id.to_string();
+            position.base_data.asset_pair = asset_pair.to_string();
+            position.base_data.base = base.to_string();
+            position.base_data.quote = "USD".to_string();
+            position.base_data.collateral = "USD".to_string();
+            position.state.open_data.asset_open_price = 1.0;
+            position.state.asset_active_price = 1.0;
+            cache.0.add_position(position);
+        }
+
+        let prices_cache = MtBidAskCache::from_iter(vec![MtBidAsk {
+            asset_pair: "EURUSD".to_string(),
+            bid: 1.1,
+            ask: 1.2,
+            base: "EUR".to_string(),
+            quote: "USD".to_string(),
+            date: DateTimeAsMicroseconds::now(),
+        }]);
+
+        let result = bulk_close_positions(
+            &mut cache,
+            EngineCacheQueryBuilder::new().with_account("account"),
+            MtPositionCloseReason::ForceClose,
+            &prices_cache,
+            "admin".to_string(),
+        )
+        .unwrap();
+
+        assert_eq!(result.closed_positions.len(), 1);
+        assert_eq!(result.skipped_position_ids, vec!["2".to_string()]);
+
+        let closed = &result.closed_positions[0];
+        assert_eq!(closed.base_data.id, "1");
+        assert_eq!(closed.state.active_state.asset_active_price, 1.1);
+        assert!(closed.state.active_state.profit > 0.0);
+        assert!(matches!(
+            closed.state.close_reason,
+            MtPositionCloseReason::ForceClose
+        ));
+
+        assert!(cache.0.get_by_id("1").is_none());
+        assert!(cache.0.get_by_id("2").is_some());
+        assert!(cache.0.get_by_id("3").is_some());
+    }
+}
@@ -0,0 +1,82 @@
+use crate::{
+    convert_position_to_closed, update_position_pl, MtEngineError, MtInstrument,
+    MtInstrumentStatus, MtPosition, MtPositionActiveState, MtPositionCloseReason,
+    MtPositionClosedState,
+};
+
+pub fn close_delisted_position(
+    mut position: MtPosition<MtPositionActiveState>,
+    instrument: &MtInstrument,
+    process_id: String,
+) -> Result<MtPosition<MtPositionClosedState>, MtEngineError> {
+    if let MtInstrumentStatus::Delisting { reference_price } = instrument.status {
+        position.state.asset_active_price = reference_price;
+        update_position_pl(&mut position)?;
+    }
+
+    Ok(convert_position_to_closed(
+        position,
+        MtPositionCloseReason::ForceClose,
+        process_id,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        close_delisted_position, get_close_reason, MtInstrument, MtInstrumentStatus, MtPosition,
+        MtPositionActiveState, MtPositionCloseReason, TestEntity,
+    };
+
+    #[test]
+    fn test_instrument_status_close_reason() {
+        let mut position: MtPosition<MtPositionActiveState> = MtPosition::generate_test_entity();
+        position.base_data.sl_price = Some(position.state.asset_active_price + 1.0);
+
+        let mut instrument = MtInstrument::generate_test_entity();
+        assert!(matches!(
+            get_close_reason(&position, &instrument).unwrap(),
+            Some(MtPositionCloseReason::StopLoss)
+        ));
+
+        instrument.status = MtInstrumentStatus::CloseOnly;
+        assert!(matches!(
+            get_close_reason(&position, &instrument).unwrap(),
+            Some(MtPositionCloseReason::StopLoss)
+        ));
+
+        instrument.status = MtInstrumentStatus::Halted;
+        assert!(get_close_reason(&position, &instrument).unwrap().is_none());
+
+        position.base_data.sl_price = None;
+        instrument.status = MtInstrumentStatus::Delisting {
+            reference_price: 20.0,
+        };
+        assert!(matches!(
+            get_close_reason(&position, &instrument).unwrap(),
+            Some(MtPositionCloseReason::ForceClose)
+        ));
+    }
+
+    #[test]
+    fn test_close_delisted_position() {
+        let mut position: MtPosition<MtPositionActiveState> = MtPosition::generate_test_entity();
+        position.base_data.collateral = "quote".to_string();
+        position.state.open_data.asset_open_price = 25.0;
+
+        let mut instrument = MtInstrument::generate_test_entity();
+        instrument.status = MtInstrumentStatus::Delisting {
+            reference_price: 20.0,
+        };
+
+        let closed =
+            close_delisted_position(position, &instrument, "delisting".to_string()).unwrap();
+
+        assert_eq!(closed.state.active_state.asset_active_price, 20.0);
+        assert!(closed.state.active_state.profit < 0.0);
+        assert!(matches!(
+            closed.state.close_reason,
+            MtPositionCloseReason::ForceClose
+        ));
+    }
+}
@@ -0,0 +1,186 @@
+use crate::{
+    convert_position_to_closed, get_price_for_profit, update_position_pl, MtCloseExecutionPolicy,
+    MtEngineError, MtPosition, MtPositionActiveState, MtPositionCloseReason, MtPositionClosedState,
+    MtPositionSide,
+};
+
+pub fn get_close_execution_price(
+    position: &MtPosition<MtPositionActiveState>,
+    close_reason: &MtPositionCloseReason,
+    policy: &MtCloseExecutionPolicy,
+) -> f64 {
+    let market_price = position.state.asset_active_price;
+
+    let level = match close_reason {
+        MtPositionCloseReason::StopLoss => get_level_price(
+            position,
+            position.base_data.sl_profit,
+            position.base_data.sl_price,
+        )
+        .filter(|sl_price| match position.base_data.side {
+            MtPositionSide::Buy => market_price <= *sl_price,
+            MtPositionSide::Sell => market_price >= *sl_price,
+        }),
+        MtPositionCloseReason::TakeProfit => get_level_price(
+            position,
+            position.base_data.tp_profit,
+            position.base_data.tp_price,
+        )
+        .filter(|tp_price| match position.base_data.side {
+            MtPositionSide::Buy => market_price >= *tp_price,
+            MtPositionSide::Sell => market_price <= *tp_price,
+        }),
+        _ => None,
+    };
+
+    let Some(level) = level else {
+        return market_price;
+    };
+
+    match policy {
+        MtCloseExecutionPolicy::Market => market_price,
+        MtCloseExecutionPolicy::GuaranteedLevel => level,
+        MtCloseExecutionPolicy::TakeProfitLevelCappedByMarket => match close_reason {
+            MtPositionCloseReason::TakeProfit => match position.base_data.side {
+                MtPositionSide::Buy => level.min(market_price),
+                MtPositionSide::Sell => level.max(market_price),
+            },
+            _ => market_price,
+        },
+    }
+}
+
+fn get_level_price(
+    position: &MtPosition<MtPositionActiveState>,
+    profit: Option<f64>,
+    price: Option<f64>,
+) -> Option<f64> {
+    match profit {
+        Some(profit) => get_price_for_profit(position, profit),
+        None => price,
+    }
+}
+
+pub fn close_position_with_policy(
+    mut position: MtPosition<MtPositionActiveState>,
+    close_reason: MtPositionCloseReason,
+    policy: &MtCloseExecutionPolicy,
+    process_id: String,
+) -> Result<MtPosition<MtPositionClosedState>, MtEngineError> {
+    let execution_price = get_close_execution_price(&position, &close_reason, policy);
+
+    if execution_price != position.state.asset_active_price {
+        let price_shift = execution_price - position.state.asset_active_price;
+
+        position.state.asset_active_price = execution_price;
+        position.state.asset_active_bid_ask.bid += price_shift;
+        position.state.asset_active_bid_ask.ask += price_shift;
+        update_position_pl(&mut position)?;
+    }
+
+    Ok(convert_position_to_closed(
+        position,
+        close_reason,
+        process_id,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        close_position_with_policy, get_close_execution_price, MtCloseExecutionPolicy, MtPosition,
+        MtPositionActiveState, MtPositionCloseReason, MtPositionSide, TestEntity,
+    };
+
+    #[test]
+    fn test_stop_loss_gap() {
+        let mut position: MtPosition<MtPositionActiveState> = MtPosition::generate_test_entity();
+        position.base_data.side = MtPositionSide::Buy;
+        position.base_data.collateral = "quote".to_string();
+        position.base_data.leverage = 10.0;
+        position.state.asset_active_price = 8.0;
+        position.state.asset_active_bid_ask.bid = 8.0;
+        position.state.asset_active_bid_ask.ask = 8.0;
+        position.base_data.sl_price = Some(9.0);
+
+        let reason = MtPositionCloseReason::StopLoss;
+
+        assert_eq!(
+            get_close_execution_price(&position, &reason, &MtCloseExecutionPolicy::Market),
+            8.0
+        );
+        assert_eq!(
+            get_close_execution_price(&position, &reason, &MtCloseExecutionPolicy::GuaranteedLevel),
+            9.0
+        );
+        assert_eq!(
+            get_close_execution_price(
+                &position,
+                &reason,
+                &MtCloseExecutionPolicy::TakeProfitLevelCappedByMarket
+            ),
+            8.0
+        );
+
+        let closed = close_position_with_policy(
+            position,
+            reason,
+            &MtCloseExecutionPolicy::GuaranteedLevel,
+            "process".to_string(),
+        )
+        .unwrap();
+
+        assert_eq!(closed.state.asset_close_price, 9.0);
+        assert_eq!(closed.state.asset_close_bid_ask.bid, 9.0);
+        assert_eq!(
+            format!("{:.2}", closed.state.active_state.profit),
+            "-100.00"
+        );
+    }
+
+    #[test]
+    fn test_take_profit_capped_by_market() {
+        let mut position: MtPosition<MtPositionActiveState> = MtPosition::generate_test_entity();
+        position.base_data.side = MtPositionSide::Sell;
+        position.base_data.collateral = "quote".to_string();
+        position.base_data.leverage = 10.0;
+        position.state.asset_active_price = 7.0;
+        position.state.asset_active_bid_ask.bid = 7.0;
+        position.state.asset_active_bid_ask.ask = 7.0;
+        position.base_data.tp_price = Some(8.0);
+
+        let reason = MtPositionCloseReason::TakeProfit;
+
+        assert_eq!(
+            get_close_execution_price(&position, &reason, &MtCloseExecutionPolicy::Market),
+            7.0
+        );
+        assert_eq!(
+            get_close_execution_price(
+                &position,
+                &reason,
+                &MtCloseExecutionPolicy::TakeProfitLevelCappedByMarket
+            ),
+            8.0
+        );
+
+        position.base_data.tp_price = None;
+        position.base_data.tp_profit = Some(10.0);
+
+        assert_eq!(
+            format!(
+                "{:.4}",
+                get_close_execution_price(
+                    &position,
+                    &reason,
+                    &MtCloseExecutionPolicy::GuaranteedLevel
+                )
+            ),
+            "9.9000"
+        );
+        assert_eq!(
+            get_close_execution_price(&position, &reason, &MtCloseExecutionPolicy::Market),
+            7.0
+        );
+    }
+}
@@ -1,12 +1,27 @@
+mod bulk_close_positions;
+mod close_delisted_position;
+mod close_position_with_policy;
+mod settlement;
+
+pub use bulk_close_positions::*;
+pub use close_delisted_position::*;
+pub use close_position_with_policy::*;
+pub use settlement::*;
+
 use rust_extensions::date_time::DateTimeAsMicroseconds;
 
-use crate::{MtPosition, MtPositionActiveState, MtPositionCloseReason, MtPositionClosedState};
+use crate::{
+    settle_close_fee, MtPosition, MtPositionActiveState, MtPositionCloseReason,
+    MtPositionClosedState,
+};
 
 pub fn convert_position_to_closed(
-    position: MtPosition<MtPositionActiveState>,
+    mut position: MtPosition<MtPositionActiveState>,
     close_reason: MtPositionCloseReason,
     process_id: String,
 ) -> MtPosition<MtPositionClosedState> {
+    settle_close_fee(&mut position);
+
     let state = MtPositionClosedState {
         asset_close_price: position.state.asset_active_price.clone(),
         asset_close_bid_ask: position.state.asset_active_bid_ask.clone(),
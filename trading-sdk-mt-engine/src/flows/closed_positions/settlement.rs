@@ -0,0 +1,78 @@
+use crate::{
+    get_position_total_invest, round_mt_amount, to_mt_amount, MtEngineError, MtInstrument,
+    MtNumber, MtPosition, MtPositionClosedState,
+};
+
+pub fn round_instrument_amount(
+    instrument: &MtInstrument,
+    amount: f64,
+) -> Result<f64, MtEngineError> {
+    let Some(rounding) = &instrument.amount_rounding else {
+        return Ok(amount);
+    };
+
+    round_mt_amount(amount, rounding)
+}
+
+pub fn get_position_settlement_profit(
+    position: &MtPosition<MtPositionClosedState>,
+    instrument: &MtInstrument,
+) -> Result<f64, MtEngineError> {
+    round_instrument_amount(instrument, position.state.active_state.profit)
+}
+
+pub fn get_position_settlement_amount(
+    position: &MtPosition<MtPositionClosedState>,
+    instrument: &MtInstrument,
+) -> Result<f64, MtEngineError> {
+    let active_position = MtPosition {
+        state: position.state.active_state.clone(),
+        base_data: position.base_data.clone(),
+    };
+
+    let amount = to_mt_amount(get_position_total_invest(&active_position)?)?
+        + to_mt_amount(get_position_settlement_profit(position, instrument)?)?;
+
+    round_instrument_amount(instrument, amount.to_f64()?)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        convert_position_to_closed, get_position_settlement_amount, get_position_settlement_profit,
+        MtAmountRounding, MtInstrument, MtPosition, MtPositionActiveState, MtPositionCloseReason,
+        MtRoundingMode, TestEntity,
+    };
+
+    #[test]
+    fn test_settlement_rounding() {
+        let mut position = MtPosition::<MtPositionActiveState>::generate_test_entity();
+        position.state.profit = 12.3456;
+        position.state.topping_up = Some(10.0);
+        let position = convert_position_to_closed(
+            position,
+            MtPositionCloseReason::ClientCommand,
+            "p".to_string(),
+        );
+
+        let mut instrument = MtInstrument::generate_test_entity();
+        assert_eq!(
+            get_position_settlement_profit(&position, &instrument).unwrap(),
+            12.3456
+        );
+
+        instrument.amount_rounding = Some(MtAmountRounding {
+            decimals: 2,
+            mode: MtRoundingMode::Down,
+        });
+
+        assert_eq!(
+            get_position_settlement_profit(&position, &instrument).unwrap(),
+            12.34
+        );
+        assert_eq!(
+            get_position_settlement_amount(&position, &instrument).unwrap(),
+            position.base_data.invest_amount + 10.0 + 12.34
+        );
+    }
+}
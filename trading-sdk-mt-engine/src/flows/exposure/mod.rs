@@ -0,0 +1,3 @@
+mod position_exposure;
+
+pub use position_exposure::*;
@@ -0,0 +1,77 @@
+use std::collections::HashMap;
+
+use crate::{
+    get_close_price, get_position_base_volume, MtBidAsk, MtBidAskCache, MtEngineError,
+    MtExposureTotals, MtPosition, MtPositionActiveState, MtPositionExposure, MtPositionSide,
+};
+
+pub fn get_position_exposure(position: &MtPosition<MtPositionActiveState>) -> MtPositionExposure {
+    let base_amount = match position.base_data.side {
+        MtPositionSide::Buy => get_position_base_volume(position),
+        MtPositionSide::Sell => -get_position_base_volume(position),
+    };
+
+    MtPositionExposure {
+        position_id: position.base_data.id.clone(),
+        asset_pair: position.base_data.asset_pair.clone(),
+        base: position.base_data.base.clone(),
+        quote: position.base_data.quote.clone(),
+        side: position.base_data.side.clone(),
+        group_id: position.base_data.group_id.clone(),
+        base_amount,
+        quote_amount: -base_amount * position.state.asset_active_price,
+    }
+}
+
+pub fn update_exposure_rate(exposure: &mut MtPositionExposure, bid_ask: &MtBidAsk) {
+    let price = get_close_price(bid_ask, &exposure.side);
+    exposure.quote_amount = -exposure.base_amount * price;
+}
+
+pub fn convert_currency_exposure(
+    totals: &MtExposureTotals,
+    reporting_currency: &str,
+    prices_cache: &MtBidAskCache,
+) -> Result<HashMap<String, f64>, MtEngineError> {
+    let mut result = HashMap::new();
+
+    for (currency, amount) in &totals.currencies {
+        let rate = get_conversion_rate(prices_cache, currency, reporting_currency)?;
+        result.insert(currency.clone(), amount * rate);
+    }
+
+    Ok(result)
+}
+
+pub fn get_total_exposure_in_currency(
+    totals: &MtExposureTotals,
+    reporting_currency: &str,
+    prices_cache: &MtBidAskCache,
+) -> Result<f64, MtEngineError> {
+    let converted = convert_currency_exposure(totals, reporting_currency, prices_cache)?;
+
+    Ok(converted.values().map(|amount| amount.abs()).sum())
+}
+
+fn get_conversion_rate(
+    prices_cache: &MtBidAskCache,
+    currency: &str,
+    reporting_currency: &str,
+) -> Result<f64, MtEngineError> {
+    if currency == reporting_currency {
+        return Ok(1.0);
+    }
+
+    if let Some(bid_ask) = prices_cache.get_base_quote(currency, reporting_currency) {
+        return Ok((bid_ask.bid + bid_ask.ask) / 2.0);
+    }
+
+    if let Some(bid_ask) = prices_cache.get_base_quote(reporting_currency, currency) {
+        return Ok(2.0 / (bid_ask.bid + bid_ask.ask));
+    }
+
+    Err(MtEngineError::NoConversionLiquidity {
+        asset: currency.to_string(),
+        collateral: reporting_currency.to_string(),
+    })
+}
@@ -0,0 +1,97 @@
+use crate::{
+    MtEngineError, MtHedgeFillResult, MtHedgeOrder, MtHedgingController, MtLiquidityProvider,
+    MtLiquidityProviderOrder,
+};
+
+pub async fn execute_hedge_order(
+    controller: &mut MtHedgingController,
+    order: MtHedgeOrder,
+    liquidity_provider: &impl MtLiquidityProvider,
+) -> Result<MtHedgeFillResult, MtEngineError> {
+    let result = liquidity_provider
+        .execute_order(MtLiquidityProviderOrder {
+            position_id: order.id.clone(),
+            account_id: controller.hedge_account_id.clone(),
+            asset_pair: order.asset_pair.clone(),
+            side: order.side.clone(),
+            volume: order.volume,
+            requested_price: order.requested_price,
+            price_limit: None,
+            process_id: order.id.clone(),
+        })
+        .await;
+
+    match result {
+        Ok(fill) => {
+            controller
+                .handle_hedge_fill(&order.id, &fill)
+                .ok_or(MtEngineError::PositionNotFound {
+                    position_id: order.id,
+                })
+        }
+        Err(err) => {
+            controller.handle_hedge_rejected(&order.id);
+            Err(err)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        execute_hedge_order, MtBidAsk, MtExposureBook, MtFakeLiquidityProvider, MtHedgeLimit,
+        MtHedgingController, MtPosition, MtPositionActiveState, MtPositionSide, TestEntity,
+    };
+
+    #[tokio::test]
+    async fn test_execute_hedge_order() {
+        let mut controller = MtHedgingController::new("house".to_string());
+        controller.set_limit(
+            "BASEQUOTE",
+            MtHedgeLimit {
+                max_net_volume: 0.0,
+                target_net_volume: 0.0,
+            },
+        );
+
+        let mut position: MtPosition<MtPositionActiveState> = MtPosition::generate_test_entity();
+        position.base_data.asset_pair = "BASEQUOTE".to_string();
+        position.base_data.collateral = "base".to_string();
+        position.base_data.side = MtPositionSide::Sell;
+
+        let mut book = MtExposureBook::new();
+        book.handle_position_added(&position);
+
+        let order = controller
+            .handle_bid_ask(&MtBidAsk::generate_test_entity(), &book)
+            .unwrap();
+        assert_eq!(order.side, MtPositionSide::Sell);
+
+        let liquidity_provider = MtFakeLiquidityProvider::new(0.5);
+        let fill_result = execute_hedge_order(&mut controller, order, &liquidity_provider)
+            .await
+            .unwrap();
+
+        assert_eq!(fill_result.opened_position.unwrap().open_price, 24.5);
+        assert_eq!(controller.get_house_net_volume("BASEQUOTE", &book), 0.0);
+        assert_eq!(liquidity_provider.get_orders()[0].account_id, "house");
+
+        let mut liquidity_provider = MtFakeLiquidityProvider::new(0.0);
+        liquidity_provider.is_rejecting = true;
+        book.handle_position_added(&{
+            let mut position = position.clone();
+            position.base_data.id = "2".to_string();
+            position
+        });
+
+        let order = controller
+            .handle_bid_ask(&MtBidAsk::generate_test_entity(), &book)
+            .unwrap();
+        assert!(
+            execute_hedge_order(&mut controller, order.clone(), &liquidity_provider)
+                .await
+                .is_err()
+        );
+        assert!(controller.get_pending_order(&order.id).is_none());
+    }
+}
@@ -0,0 +1,5 @@
+mod execute_hedge_orders;
+mod mt_hedging_controller;
+
+pub use execute_hedge_orders::*;
+pub use mt_hedging_controller::*;
@@ -0,0 +1,461 @@
+use std::collections::HashMap;
+
+use rust_extensions::date_time::DateTimeAsMicroseconds;
+
+use crate::{
+    get_close_price, get_open_price, get_position_base_volume, MtBidAsk, MtExposureBook,
+    MtHedgeFillResult, MtHedgeLimit, MtHedgeOrder, MtHedgePosition, MtHedgingPnlReport,
+    MtLiquidityProviderFill, MtPosition, MtPositionActiveState, MtPositionSide,
+};
+
+pub struct MtHedgingController {
+    pub hedge_account_id: String,
+    limits: HashMap<String, MtHedgeLimit>,
+    prices: HashMap<String, MtBidAsk>,
+    pending_orders: HashMap<String, MtHedgeOrder>,
+    hedge_positions: HashMap<String, MtHedgePosition>,
+    realized_pnl: HashMap<String, f64>,
+    orders_counter: u64,
+}
+
+impl MtHedgingController {
+    pub fn new(hedge_account_id: String) -> Self {
+        Self {
+            hedge_account_id,
+            limits: HashMap::new(),
+            prices: HashMap::new(),
+            pending_orders: HashMap::new(),
+            hedge_positions: HashMap::new(),
+            realized_pnl: HashMap::new(),
+            orders_counter: 0,
+        }
+    }
+
+    pub fn set_limit(&mut self, asset_pair: &str, limit: MtHedgeLimit) {
+        self.limits.insert(asset_pair.to_string(), limit);
+    }
+
+    pub fn remove_limit(&mut self, asset_pair: &str) -> Option<MtHedgeLimit> {
+        self.limits.remove(asset_pair)
+    }
+
+    pub fn handle_bid_ask(
+        &mut self,
+        bid_ask: &MtBidAsk,
+        exposure_book: &MtExposureBook,
+    ) -> Option<MtHedgeOrder> {
+        for hedge_position in self.hedge_positions.values_mut() {
+            if hedge_position.asset_pair == bid_ask.asset_pair {
+                hedge_position.active_price = get_close_price(bid_ask, &hedge_position.side);
+            }
+        }
+
+        self.prices
+            .insert(bid_ask.asset_pair.clone(), bid_ask.clone());
+
+        self.check_exposure(&bid_ask.asset_pair, exposure_book)
+    }
+
+    pub fn check_exposure(
+        &mut self,
+        asset_pair: &str,
+        exposure_book: &MtExposureBook,
+    ) -> Option<MtHedgeOrder> {
+        let limit = self.limits.get(asset_pair)?;
+        let bid_ask = self.prices.get(asset_pair)?;
+
+        let house_net_volume = self.get_house_net_volume(asset_pair, exposure_book);
+
+        if house_net_volume.abs() <= limit.max_net_volume {
+            return None;
+        }
+
+        let target_net_volume =
+            limit.target_net_volume.min(limit.max_net_volume) * house_net_volume.signum();
+        let delta = target_net_volume - house_net_volume;
+
+        let side = match delta > 0.0 {
+            true => MtPositionSide::Buy,
+            false => MtPositionSide::Sell,
+        };
+
+        self.orders_counter += 1;
+
+        let order = MtHedgeOrder {
+            id: format!("hedge-{}-{}", asset_pair, self.orders_counter),
+            asset_pair: asset_pair.to_string(),
+            requested_price: get_open_price(bid_ask, &side),
+            side,
+            volume: delta.abs(),
+            date: DateTimeAsMicroseconds::now(),
+        };
+
+        self.pending_orders.insert(order.id.clone(), order.clone());
+
+        Some(order)
+    }
+
+    /// Fills against opposite hedge positions reduce them first, oldest first,
+    /// and only the remaining volume opens a new hedge position.
+    pub fn handle_hedge_fill(
+        &mut self,
+        order_id: &str,
+        fill: &MtLiquidityProviderFill,
+    ) -> Option<MtHedgeFillResult> {
+        let order = self.pending_orders.remove(order_id)?;
+
+        let mut opposite_positions: Vec<&MtHedgePosition> = self
+            .get_hedge_positions(&order.asset_pair)
+            .into_iter()
+            .filter(|hedge_position| hedge_position.side != order.side)
+            .collect();
+        opposite_positions.sort_by_key(|hedge_position| hedge_position.open_date.unix_microseconds);
+        let opposite_ids: Vec<String> = opposite_positions
+            .iter()
+            .map(|hedge_position| hedge_position.id.clone())
+            .collect();
+
+        let mut remaining_volume = order.volume;
+        let mut realized_pnl = 0.0;
+
+        for id in opposite_ids {
+            if remaining_volume <= 0.0 {
+                break;
+            }
+
+            let hedge_position = self.hedge_positions.get_mut(&id)?;
+            let reduced_volume = hedge_position.volume.min(remaining_volume);
+            let direction = hedge_position.get_signed_volume().signum();
+
+            realized_pnl += (fill.price - hedge_position.open_price) * reduced_volume * direction;
+            hedge_position.volume -= reduced_volume;
+            remaining_volume -= reduced_volume;
+
+            if hedge_position.volume <= 0.0 {
+                self.hedge_positions.remove(&id);
+            }
+        }
+
+        *self
+            .realized_pnl
+            .entry(order.asset_pair.clone())
+            .or_default() += realized_pnl;
+
+        let opened_position = match remaining_volume > 0.0 {
+            true => {
+                let active_price = match self.prices.get(&order.asset_pair) {
+                    Some(bid_ask) => get_close_price(bid_ask, &order.side),
+                    None => fill.price,
+                };
+
+                let hedge_position = MtHedgePosition {
+                    id: order.id.clone(),
+                    asset_pair: order.asset_pair,
+                    side: order.side,
+                    volume: remaining_volume,
+                    open_price: fill.price,
+                    active_price,
+                    open_date: fill.date,
+                };
+
+                self.hedge_positions
+                    .insert(hedge_position.id.clone(), hedge_position.clone());
+
+                Some(hedge_position)
+            }
+            false => None,
+        };
+
+        Some(MtHedgeFillResult {
+            order_id: order.id,
+            reduced_volume: order.volume - remaining_volume,
+            realized_pnl,
+            opened_position,
+        })
+    }
+
+    pub fn handle_hedge_rejected(&mut self, order_id: &str) -> Option<MtHedgeOrder> {
+        self.pending_orders.remove(order_id)
+    }
+
+    pub fn get_pending_order(&self, order_id: &str) -> Option<&MtHedgeOrder> {
+        self.pending_orders.get(order_id)
+    }
+
+    pub fn get_hedge_positions(&self, asset_pair: &str) -> Vec<&MtHedgePosition> {
+        self.hedge_positions
+            .values()
+            .filter(|hedge_position| hedge_position.asset_pair == asset_pair)
+            .collect()
+    }
+
+    pub fn get_hedged_volume(&self, asset_pair: &str) -> f64 {
+        let filled: f64 = self
+            .get_hedge_positions(asset_pair)
+            .iter()
+            .map(|hedge_position| hedge_position.get_signed_volume())
+            .sum();
+
+        let pending: f64 = self
+            .pending_orders
+            .values()
+            .filter(|order| order.asset_pair == asset_pair)
+            .map(|order| order.get_signed_volume())
+            .sum();
+
+        filled + pending
+    }
+
+    pub fn get_house_net_volume(&self, asset_pair: &str, exposure_book: &MtExposureBook) -> f64 {
+        let client_net_volume = exposure_book
+            .get_totals()
+            .get_pair(asset_pair)
+            .map(|pair| pair.get_net_volume())
+            .unwrap_or(0.0);
+
+        -client_net_volume + self.get_hedged_volume(asset_pair)
+    }
+
+    pub fn get_pnl_report(
+        &self,
+        asset_pair: &str,
+        client_positions: &[&MtPosition<MtPositionActiveState>],
+    ) -> MtHedgingPnlReport {
+        let mut client_net_volume = 0.0;
+        let mut client_pnl = 0.0;
+
+        for position in client_positions
+            .iter()
+            .filter(|position| position.base_data.asset_pair == asset_pair)
+        {
+            client_net_volume += match position.base_data.side {
+                MtPositionSide::Buy => get_position_base_volume(position),
+                MtPositionSide::Sell => -get_position_base_volume(position),
+            };
+            client_pnl += get_position_quote_profit(position);
+        }
+
+        let hedge_positions = self.get_hedge_positions(asset_pair);
+
+        let hedged_net_volume = hedge_positions
+            .iter()
+            .map(|hedge_position| hedge_position.get_signed_volume())
+            .sum();
+        let hedge_pnl: f64 = hedge_positions
+            .iter()
+            .map(|hedge_position| hedge_position.get_profit())
+            .sum::<f64>()
+            + self.realized_pnl.get(asset_pair).copied().unwrap_or(0.0);
+
+        let unhedged_pnl = -client_pnl;
+
+        MtHedgingPnlReport {
+            asset_pair: asset_pair.to_string(),
+            client_net_volume,
+            hedged_net_volume,
+            unhedged_pnl,
+            hedge_pnl,
+            hedged_pnl: unhedged_pnl + hedge_pnl,
+        }
+    }
+}
+
+fn get_position_quote_profit(position: &MtPosition<MtPositionActiveState>) -> f64 {
+    let profit = position.state.profit;
+
+    if position.base_data.quote == position.base_data.collateral {
+        return profit;
+    }
+
+    if position.base_data.base == position.base_data.collateral {
+        return profit * position.state.asset_active_price;
+    }
+
+    let inverted_quote = match &position.state.quote_collateral_active_bid_ask {
+        Some(bid_ask) => bid_ask.quote != position.base_data.collateral,
+        None => false,
+    };
+
+    match inverted_quote {
+        true => profit * position.state.quote_collateral_active_price,
+        false => profit / position.state.quote_collateral_active_price,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_extensions::date_time::DateTimeAsMicroseconds;
+
+    use crate::{
+        update_position_pl, MtBidAsk, MtExposureBook, MtHedgeLimit, MtHedgingController,
+        MtLiquidityProviderFill, MtPosition, MtPositionActiveState, MtPositionSide, TestEntity,
+    };
+
+    fn create_bid_ask(price: f64) -> MtBidAsk {
+        let mut bid_ask = MtBidAsk::generate_test_entity();
+        bid_ask.bid = price;
+        bid_ask.ask = price;
+        bid_ask
+    }
+
+    fn create_controller() -> MtHedgingController {
+        let mut controller = MtHedgingController::new("house".to_string());
+        controller.set_limit(
+            "BASEQUOTE",
+            MtHedgeLimit {
+                max_net_volume: 1500.0,
+                target_net_volume: 500.0,
+            },
+        );
+        controller
+    }
+
+    #[test]
+    fn test_hedge_order_generation() {
+        let mut controller = create_controller();
+        let mut position: MtPosition<MtPositionActiveState> = MtPosition::generate_test_entity();
+        position.base_data.asset_pair = "BASEQUOTE".to_string();
+        position.base_data.collateral = "base".to_string();
+        position.base_data.leverage = 10.0;
+        position.state.open_data.asset_open_price = 25.0;
+        position.state.asset_active_price = 25.0;
+        let mut book = MtExposureBook::new();
+
+        position.base_data.id = "1".to_string();
+        book.handle_position_added(&position);
+
+        assert!(controller
+            .handle_bid_ask(&create_bid_ask(25.0), &book)
+            .is_none());
+
+        position.base_data.id = "2".to_string();
+        book.handle_position_added(&position);
+        let order = controller
+            .handle_bid_ask(&create_bid_ask(25.0), &book)
+            .unwrap();
+
+        assert_eq!(order.side, MtPositionSide::Buy);
+        assert_eq!(order.volume, 1500.0);
+        assert_eq!(controller.get_house_net_volume("BASEQUOTE", &book), -500.0);
+
+        assert!(controller
+            .handle_bid_ask(&create_bid_ask(25.0), &book)
+            .is_none());
+
+        controller.handle_hedge_rejected(&order.id);
+        assert_eq!(controller.get_house_net_volume("BASEQUOTE", &book), -2000.0);
+    }
+
+    #[test]
+    fn test_hedged_pnl_report() {
+        let mut controller = create_controller();
+        let mut position: MtPosition<MtPositionActiveState> = MtPosition::generate_test_entity();
+        position.base_data.asset_pair = "BASEQUOTE".to_string();
+        position.base_data.collateral = "base".to_string();
+        position.base_data.leverage = 10.0;
+        position.state.open_data.asset_open_price = 25.0;
+        position.state.asset_active_price = 25.0;
+        let mut book = MtExposureBook::new();
+        let mut positions = vec![];
+
+        for id in ["1", "2"] {
+            position.base_data.id = id.to_string();
+            book.handle_position_added(&position);
+            positions.push(position.clone());
+        }
+
+        let order = controller
+            .handle_bid_ask(&create_bid_ask(25.0), &book)
+            .unwrap();
+
+        controller
+            .handle_hedge_fill(
+                &order.id,
+                &MtLiquidityProviderFill {
+                    price: 25.0,
+                    date: DateTimeAsMicroseconds::now(),
+                },
+            )
+            .unwrap();
+
+        controller.handle_bid_ask(&create_bid_ask(26.0), &book);
+
+        for position in positions.iter_mut() {
+            position.state.asset_active_price = 26.0;
+            update_position_pl(position).unwrap();
+        }
+        let client_positions: Vec<&MtPosition<MtPositionActiveState>> = positions.iter().collect();
+
+        let report = controller.get_pnl_report("BASEQUOTE", &client_positions);
+
+        assert_eq!(report.client_net_volume, 2000.0);
+        assert_eq!(report.hedged_net_volume, 1500.0);
+        assert_eq!(report.unhedged_pnl, -2000.0);
+        assert_eq!(report.hedge_pnl, 1500.0);
+        assert_eq!(report.hedged_pnl, -500.0);
+    }
+
+    #[test]
+    fn test_shrinking_exposure_closes_hedges() {
+        let mut controller = create_controller();
+        let mut position: MtPosition<MtPositionActiveState> = MtPosition::generate_test_entity();
+        position.base_data.asset_pair = "BASEQUOTE".to_string();
+        position.base_data.collateral = "base".to_string();
+        position.base_data.leverage = 10.0;
+        position.state.open_data.asset_open_price = 25.0;
+        let mut book = MtExposureBook::new();
+
+        for id in ["1", "2"] {
+            position.base_data.id = id.to_string();
+            book.handle_position_added(&position);
+        }
+
+        let order = controller
+            .handle_bid_ask(&create_bid_ask(25.0), &book)
+            .unwrap();
+        controller
+            .handle_hedge_fill(
+                &order.id,
+                &MtLiquidityProviderFill {
+                    price: 25.0,
+                    date: DateTimeAsMicroseconds::now(),
+                },
+            )
+            .unwrap();
+
+        book.handle_position_removed("1");
+        book.handle_position_removed("2");
+        controller.set_limit(
+            "BASEQUOTE",
+            MtHedgeLimit {
+                max_net_volume: 1000.0,
+                target_net_volume: 0.0,
+            },
+        );
+
+        let order = controller
+            .handle_bid_ask(&create_bid_ask(26.0), &book)
+            .unwrap();
+        assert_eq!(order.side, MtPositionSide::Sell);
+        assert_eq!(order.volume, 1500.0);
+
+        let result = controller
+            .handle_hedge_fill(
+                &order.id,
+                &MtLiquidityProviderFill {
+                    price: 26.0,
+                    date: DateTimeAsMicroseconds::now(),
+                },
+            )
+            .unwrap();
+
+        assert_eq!(result.reduced_volume, 1500.0);
+        assert_eq!(result.realized_pnl, 1500.0);
+        assert!(result.opened_position.is_none());
+        assert!(controller.get_hedge_positions("BASEQUOTE").is_empty());
+
+        let report = controller.get_pnl_report("BASEQUOTE", &[]);
+        assert_eq!(report.hedged_net_volume, 0.0);
+        assert_eq!(report.hedge_pnl, 1500.0);
+    }
+}
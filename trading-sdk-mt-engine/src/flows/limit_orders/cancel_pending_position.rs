@@ -0,0 +1,98 @@
+use rust_extensions::date_time::DateTimeAsMicroseconds;
+
+use crate::{
+    MtEngineError, MtPosition, MtPositionCancelReason, MtPositionCanceledState,
+    MtPositionPendingState, PendingPositionsCache,
+};
+
+pub fn convert_position_to_canceled(
+    position: MtPosition<MtPositionPendingState>,
+    cancel_reason: MtPositionCancelReason,
+    process_id: String,
+) -> MtPosition<MtPositionCanceledState> {
+    let state = MtPositionCanceledState {
+        pending_state: position.state,
+        cancel_reason,
+        cancel_process_id: process_id,
+        cancel_date: DateTimeAsMicroseconds::now(),
+    };
+
+    MtPosition {
+        state,
+        base_data: position.base_data,
+    }
+}
+
+pub fn cancel_pending_position(
+    cache: &mut PendingPositionsCache,
+    position_id: &str,
+    cancel_reason: MtPositionCancelReason,
+    process_id: String,
+) -> Result<MtPosition<MtPositionCanceledState>, MtEngineError> {
+    if cache.0.get_by_id(position_id).is_none() {
+        return Err(MtEngineError::PositionNotFound {
+            position_id: position_id.to_string(),
+        });
+    }
+
+    let position =
+        cache
+            .0
+            .remove_position(position_id)
+            .ok_or_else(|| MtEngineError::PositionNotFound {
+                position_id: position_id.to_string(),
+            })?;
+
+    Ok(convert_position_to_canceled(
+        position,
+        cancel_reason,
+        process_id,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        cancel_pending_position, MtEngineError, MtPosition, MtPositionBaseData,
+        MtPositionCancelReason, MtPositionPendingState, PendingPositionsCache, TestEntity,
+    };
+
+    #[test]
+    fn test_cancel_pending_position() {
+        let mut cache = PendingPositionsCache::new();
+        let position = MtPosition {
+            state: MtPositionPendingState::generate_test_entity(),
+            base_data: MtPositionBaseData::generate_test_entity(),
+        };
+        cache.0.add_position(position);
+
+        let canceled = cancel_pending_position(
+            &mut cache,
+            "id",
+            MtPositionCancelReason::ClientCommand,
+            "cancel_process".to_string(),
+        )
+        .unwrap();
+
+        assert_eq!(canceled.base_data.id, "id");
+        assert_eq!(canceled.state.pending_state.desire_price, 20.0);
+        assert_eq!(canceled.state.cancel_process_id, "cancel_process");
+        assert!(matches!(
+            canceled.state.cancel_reason,
+            MtPositionCancelReason::ClientCommand
+        ));
+        assert!(cache.0.get_by_id("id").is_none());
+
+        let result = cancel_pending_position(
+            &mut cache,
+            "id",
+            MtPositionCancelReason::ClientCommand,
+            "cancel_process".to_string(),
+        );
+
+        assert!(matches!(
+            result,
+            Err(MtEngineError::PositionNotFound { .. })
+        ));
+    }
+}
@@ -3,8 +3,11 @@ use std::collections::HashMap;
 use rust_extensions::date_time::DateTimeAsMicroseconds;
 
 use crate::{
-    get_open_price, get_pending_position_type, sanitize_sl_tp, MtBidAskCache, MtEngineError,
-    MtPosition, MtPositionBaseData, MtPositionPendingState, MtPositionSide,
+    get_pending_order_reference_price, get_pending_order_type, normalize_optional_price,
+    normalize_price, sanitize_sl_tp, validate_bracket_protection, validate_instrument_asset_pair,
+    validate_invest_amount, validate_pending_bracket, validate_price_age, validate_price_on_tick,
+    MtBidAskCache, MtEngineError, MtInstrumentsCache, MtPendingBracket, MtPendingOrderKind,
+    MtPosition, MtPositionBaseData, MtPositionPendingState, MtPositionProtection, MtPositionSide,
 };
 
 pub struct MtPositionOpenPendingCommand {
@@ -25,27 +28,97 @@ pub struct MtPositionOpenPendingCommand {
     pub sl_profit: Option<f64>,
     pub sl_price: Option<f64>,
     pub desired_open_price: f64,
+    pub order_kind: MtPendingOrderKind,
+    pub oco_group_id: Option<String>,
+    pub bracket: Option<MtPendingBracket>,
     pub margin_call_percent: Option<f64>,
     pub topping_up_percent: Option<f64>,
     pub metadata: Option<HashMap<String, String>>,
+    pub group_id: Option<String>,
 }
 
 pub fn create_pending_position(
     command: MtPositionOpenPendingCommand,
     prices_cache: &MtBidAskCache,
+    instruments_cache: &MtInstrumentsCache,
 ) -> Result<MtPosition<MtPositionPendingState>, MtEngineError> {
-    let asset_price = prices_cache
+    let instrument = instruments_cache
         .get_by_id(&command.asset_pair)
-        .ok_or(MtEngineError::NoLiquidity)?;
+        .ok_or_else(|| MtEngineError::InstrumentNotFound {
+            asset_pair: command.asset_pair.clone(),
+        })?;
 
-    let current_price = get_open_price(asset_price.as_ref(), &command.side);
+    let asset_price =
+        prices_cache
+            .get_by_id(&command.asset_pair)
+            .ok_or_else(|| MtEngineError::NoLiquidity {
+                asset_pair: command.asset_pair.clone(),
+            })?;
 
-    let position_type =
-        get_pending_position_type(current_price, command.desired_open_price, &command.side);
+    let validation_result =
+        validate_instrument_asset_pair(&instrument, &asset_price, &command.base, &command.quote)
+            .and_then(|_| {
+                validate_invest_amount(&instrument, command.invest_amount, command.leverage)
+            })
+            .and_then(|_| validate_price_on_tick(&instrument, Some(command.desired_open_price)))
+            .and_then(|_| validate_price_on_tick(&instrument, command.tp_price))
+            .and_then(|_| validate_price_on_tick(&instrument, command.sl_price));
+
+    if let Err(violation) = validation_result {
+        return Err(MtEngineError::validation_failed(&command.id, violation));
+    }
+
+    validate_price_age(&instrument, &asset_price, DateTimeAsMicroseconds::now())?;
+
+    let current_price =
+        get_pending_order_reference_price(asset_price.as_ref(), &command.side, &command.order_kind);
+    let desired_open_price = normalize_price(&instrument, command.desired_open_price);
+
+    let position_type = get_pending_order_type(
+        current_price,
+        desired_open_price,
+        &command.side,
+        &command.order_kind,
+    )
+    .map_err(|violation| MtEngineError::validation_failed(&command.id, violation))?;
+
+    let limit_price = match &command.order_kind {
+        MtPendingOrderKind::StopLimit { limit_price } => Some(*limit_price),
+        _ => None,
+    };
+
+    let protection = MtPositionProtection {
+        tp_profit: command.tp_profit,
+        tp_price: command.tp_price,
+        sl_profit: command.sl_profit,
+        sl_price: command.sl_price,
+    };
+
+    let validation_result =
+        validate_price_on_tick(&instrument, limit_price).and_then(|_| match &command.bracket {
+            Some(bracket) => validate_pending_bracket(&instrument, bracket)
+                .and_then(|_| validate_bracket_protection(bracket, &protection)),
+            None => Ok(()),
+        });
+
+    if let Err(violation) = validation_result {
+        return Err(MtEngineError::validation_failed(&command.id, violation));
+    }
+
+    let limit_price = normalize_optional_price(&instrument, limit_price);
+
+    let bracket = command.bracket.map(|bracket| MtPendingBracket {
+        sl_distance: normalize_optional_price(&instrument, bracket.sl_distance),
+        tp_distance: normalize_optional_price(&instrument, bracket.tp_distance),
+    });
 
     let state = MtPositionPendingState {
-        desire_price: command.desired_open_price,
+        desire_price: desired_open_price,
         position_type,
+        limit_price,
+        is_triggered: false,
+        oco_group_id: command.oco_group_id,
+        bracket,
     };
 
     let mut base_data = MtPositionBaseData {
@@ -65,12 +138,13 @@ pub fn create_pending_position(
         base: command.base,
         quote: command.quote,
         tp_profit: command.tp_profit,
-        tp_price: command.tp_price,
+        tp_price: normalize_optional_price(&instrument, command.tp_price),
         sl_profit: command.sl_profit,
-        sl_price: command.sl_price,
+        sl_price: normalize_optional_price(&instrument, command.sl_price),
         topping_up_percent: command.topping_up_percent,
         metadata: command.metadata,
-        margin_call_percent: command.margin_call_percent
+        group_id: command.group_id,
+        margin_call_percent: command.margin_call_percent,
     };
 
     sanitize_sl_tp(&mut base_data);
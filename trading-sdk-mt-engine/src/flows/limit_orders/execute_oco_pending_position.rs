@@ -0,0 +1,252 @@
+use trading_sdk_core::EngineCacheQueryBuilder;
+
+use crate::{
+    convert_position_to_canceled, execute_pending_position, MtBidAskCache, MtEngineError,
+    MtFeeModelsCache, MtInstrumentsCache, MtPosition, MtPositionActiveState,
+    MtPositionCancelReason, MtPositionCanceledState, PendingPositionsCache,
+};
+
+pub struct MtPendingPositionExecutionResult {
+    pub position: MtPosition<MtPositionActiveState>,
+    pub canceled_siblings: Vec<MtPosition<MtPositionCanceledState>>,
+}
+
+pub fn execute_pending_position_from_cache(
+    cache: &mut PendingPositionsCache,
+    position_id: &str,
+    prices_cache: &MtBidAskCache,
+    instruments_cache: &MtInstrumentsCache,
+    fees_cache: &MtFeeModelsCache,
+    process_id: String,
+) -> Result<MtPendingPositionExecutionResult, MtEngineError> {
+    let Some(pending_position) = cache.0.get_by_id(position_id).cloned() else {
+        return Err(MtEngineError::PositionNotFound {
+            position_id: position_id.to_string(),
+        });
+    };
+
+    let oco_group_id = pending_position.state.oco_group_id.clone();
+    let account_id = pending_position.base_data.account_id.clone();
+
+    let position = execute_pending_position(
+        pending_position,
+        prices_cache,
+        instruments_cache,
+        fees_cache,
+        process_id.clone(),
+    )?;
+    cache.0.remove_position(position_id);
+
+    let canceled_siblings = match oco_group_id {
+        Some(oco_group_id) => cache
+            .0
+            .query_and_select_remove(
+                EngineCacheQueryBuilder::new().with_account(&account_id),
+                |sibling| sibling.state.oco_group_id.as_ref() == Some(&oco_group_id),
+            )
+            .into_iter()
+            .map(|sibling| {
+                convert_position_to_canceled(
+                    sibling,
+                    MtPositionCancelReason::OcoSiblingExecuted,
+                    process_id.clone(),
+                )
+            })
+            .collect(),
+        None => vec![],
+    };
+
+    Ok(MtPendingPositionExecutionResult {
+        position,
+        canceled_siblings,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        execute_pending_position_from_cache, MtBidAsk, MtBidAskCache, MtEngineError,
+        MtFeeModelsCache, MtInstrument, MtInstrumentStatus, MtInstrumentsCache, MtPendingBracket,
+        MtPosition, MtPositionBaseData, MtPositionCancelReason, MtPositionPendingState,
+        MtPositionSide, MtTradingSchedule, MtValidationViolation, PendingPositionsCache,
+        TestEntity,
+    };
+
+    fn create_prices_cache() -> MtBidAskCache {
+        MtBidAskCache::from_iter(vec![MtBidAsk {
+            bid: 1.0588,
+            ask: 1.0688,
+            ..MtBidAsk::generate_test_entity()
+        }])
+    }
+
+    #[test]
+    fn test_execute_cancels_oco_siblings() {
+        let mut cache = PendingPositionsCache::new();
+
+        for (id, side, oco_group_id) in [
+            ("buy", MtPositionSide::Buy, Some("group")),
+            ("sell", MtPositionSide::Sell, Some("group")),
+            ("other", MtPositionSide::Buy, None),
+        ] {
+            let mut position = MtPosition {
+                state: MtPositionPendingState::generate_test_entity(),
+                base_data: MtPositionBaseData::generate_test_entity(),
+            };
+            position.base_data.id = id.to_string();
+            position.base_data.side = side;
+            position.base_data.asset_pair = "BASEQUOTE".to_string();
+            position.base_data.collateral = "quote".to_string();
+            position.state.oco_group_id = oco_group_id.map(|x| x.to_string());
+            cache.0.add_position(position);
+        }
+
+        let result = execute_pending_position_from_cache(
+            &mut cache,
+            "buy",
+            &create_prices_cache(),
+            &MtInstrumentsCache::from_iter(vec![MtInstrument::generate_test_entity()]),
+            &MtFeeModelsCache::new(),
+            "process".to_string(),
+        )
+        .unwrap();
+
+        assert_eq!(result.position.base_data.id, "buy");
+        assert_eq!(result.canceled_siblings.len(), 1);
+        assert_eq!(result.canceled_siblings[0].base_data.id, "sell");
+        assert!(matches!(
+            result.canceled_siblings[0].state.cancel_reason,
+            MtPositionCancelReason::OcoSiblingExecuted
+        ));
+
+        assert!(cache.0.get_by_id("buy").is_none());
+        assert!(cache.0.get_by_id("sell").is_none());
+        assert!(cache.0.get_by_id("other").is_some());
+    }
+
+    #[test]
+    fn test_bracket_anchored_to_fill_price() {
+        let mut cache = PendingPositionsCache::new();
+
+        for (id, side, tp_distance) in [
+            ("buy", MtPositionSide::Buy, Some(0.02)),
+            ("sell", MtPositionSide::Sell, None),
+        ] {
+            let mut position = MtPosition {
+                state: MtPositionPendingState::generate_test_entity(),
+                base_data: MtPositionBaseData::generate_test_entity(),
+            };
+            position.base_data.id = id.to_string();
+            position.base_data.side = side;
+            position.base_data.asset_pair = "BASEQUOTE".to_string();
+            position.base_data.collateral = "quote".to_string();
+            position.state.bracket = Some(MtPendingBracket {
+                sl_distance: Some(0.02),
+                tp_distance,
+            });
+            cache.0.add_position(position);
+        }
+
+        let prices_cache = create_prices_cache();
+
+        let buy = execute_pending_position_from_cache(
+            &mut cache,
+            "buy",
+            &prices_cache,
+            &MtInstrumentsCache::from_iter(vec![MtInstrument::generate_test_entity()]),
+            &MtFeeModelsCache::new(),
+            "process".to_string(),
+        )
+        .unwrap()
+        .position;
+
+        assert_eq!(buy.state.open_data.asset_open_price, 1.0688);
+        assert_eq!(buy.base_data.sl_price, Some(1.0688 - 0.02));
+        assert_eq!(buy.base_data.tp_price, Some(1.0688 + 0.02));
+
+        let sell = execute_pending_position_from_cache(
+            &mut cache,
+            "sell",
+            &prices_cache,
+            &MtInstrumentsCache::from_iter(vec![MtInstrument::generate_test_entity()]),
+            &MtFeeModelsCache::new(),
+            "process".to_string(),
+        )
+        .unwrap()
+        .position;
+
+        assert_eq!(sell.state.open_data.asset_open_price, 1.0588);
+        assert_eq!(sell.base_data.sl_price, Some(1.0588 + 0.02));
+        assert_eq!(sell.base_data.tp_price, None);
+    }
+
+    #[test]
+    fn test_execute_rejected_when_close_only() {
+        let mut cache = PendingPositionsCache::new();
+        let mut position = MtPosition {
+            state: MtPositionPendingState::generate_test_entity(),
+            base_data: MtPositionBaseData::generate_test_entity(),
+        };
+        position.base_data.id = "buy".to_string();
+        position.base_data.asset_pair = "BASEQUOTE".to_string();
+        position.base_data.collateral = "quote".to_string();
+        cache.0.add_position(position);
+
+        let mut instrument = MtInstrument::generate_test_entity();
+        instrument.status = MtInstrumentStatus::CloseOnly;
+
+        let result = execute_pending_position_from_cache(
+            &mut cache,
+            "buy",
+            &create_prices_cache(),
+            &MtInstrumentsCache::from_iter(vec![instrument]),
+            &MtFeeModelsCache::new(),
+            "process".to_string(),
+        );
+
+        assert!(matches!(
+            result,
+            Err(MtEngineError::ValidationFailed { .. })
+        ));
+        assert!(cache.0.get_by_id("buy").is_some());
+    }
+
+    #[test]
+    fn test_execute_rejected_when_market_closed() {
+        let mut cache = PendingPositionsCache::new();
+        let mut position = MtPosition {
+            state: MtPositionPendingState::generate_test_entity(),
+            base_data: MtPositionBaseData::generate_test_entity(),
+        };
+        position.base_data.id = "buy".to_string();
+        position.base_data.asset_pair = "BASEQUOTE".to_string();
+        position.base_data.collateral = "quote".to_string();
+        cache.0.add_position(position);
+
+        let mut instrument = MtInstrument::generate_test_entity();
+        instrument.trading_schedule = Some(MtTradingSchedule {
+            timezone: "UTC".to_string(),
+            windows: vec![],
+            daily_breaks: vec![],
+            holidays: vec![],
+        });
+
+        let result = execute_pending_position_from_cache(
+            &mut cache,
+            "buy",
+            &create_prices_cache(),
+            &MtInstrumentsCache::from_iter(vec![instrument]),
+            &MtFeeModelsCache::new(),
+            "process".to_string(),
+        );
+
+        assert!(matches!(
+            result,
+            Err(MtEngineError::ValidationFailed {
+                violation: MtValidationViolation::MarketClosed,
+                ..
+            })
+        ));
+        assert!(cache.0.get_by_id("buy").is_some());
+    }
+}
@@ -1,19 +1,40 @@
 use rust_extensions::date_time::DateTimeAsMicroseconds;
 
 use crate::{
-    get_base_collateral_open_price, get_close_price, get_open_price,
-    get_quote_collateral_close_price, MtBidAskCache, MtEngineError, MtPosition,
+    calculate_position_fees, get_base_collateral_open_price, get_close_price, get_open_price,
+    get_quote_collateral_close_price, normalize_optional_price, update_position_pl,
+    validate_instrument_status, validate_protection_levels, validate_trading_session,
+    MtBidAskCache, MtEngineError, MtFeeModelsCache, MtInstrumentsCache, MtPosition,
     MtPositionActiveState, MtPositionActiveStateOpenData, MtPositionPendingState,
+    MtPositionProtection,
 };
 
 pub fn execute_pending_position(
     pending_position: MtPosition<MtPositionPendingState>,
     prices_cache: &MtBidAskCache,
+    instruments_cache: &MtInstrumentsCache,
+    fees_cache: &MtFeeModelsCache,
     process_id: String,
 ) -> Result<MtPosition<MtPositionActiveState>, MtEngineError> {
+    let instrument = instruments_cache
+        .get_by_id(&pending_position.base_data.asset_pair)
+        .ok_or_else(|| MtEngineError::InstrumentNotFound {
+            asset_pair: pending_position.base_data.asset_pair.clone(),
+        })?;
+
+    validate_instrument_status(&instrument).map_err(|violation| {
+        MtEngineError::validation_failed(&pending_position.base_data.id, violation)
+    })?;
+
     let asset_price = prices_cache
         .get_by_id(&pending_position.base_data.asset_pair)
-        .ok_or(MtEngineError::NoLiquidity)?;
+        .ok_or_else(|| MtEngineError::NoLiquidity {
+            asset_pair: pending_position.base_data.asset_pair.clone(),
+        })?;
+
+    validate_trading_session(&instrument, asset_price.date).map_err(|violation| {
+        MtEngineError::validation_failed(&pending_position.base_data.id, violation)
+    })?;
 
     let (base_collateral_open_price, base_collateral_open_bid_ask) =
         get_base_collateral_open_price(
@@ -31,8 +52,28 @@ pub fn execute_pending_position(
             &pending_position.base_data.side,
         )?;
 
+    let asset_open_price = get_open_price(asset_price.as_ref(), &pending_position.base_data.side);
+    let mut base_data = pending_position.base_data;
+
+    let asset_close_price = get_close_price(asset_price.as_ref(), &base_data.side);
+
+    if let Some(bracket) = &pending_position.state.bracket {
+        bracket.apply_to(&mut base_data, asset_open_price);
+        base_data.sl_price = normalize_optional_price(&instrument, base_data.sl_price);
+        base_data.tp_price = normalize_optional_price(&instrument, base_data.tp_price);
+
+        validate_protection_levels(
+            &instrument,
+            &MtPositionProtection::from(&base_data),
+            &base_data.side,
+            asset_close_price,
+            0.0,
+        )
+        .map_err(|violation| MtEngineError::validation_failed(&base_data.id, violation))?;
+    }
+
     let open_date = MtPositionActiveStateOpenData {
-        asset_open_price: get_open_price(asset_price.as_ref(), &pending_position.base_data.side),
+        asset_open_price,
         asset_open_bid_ask: asset_price.as_ref().clone(),
         base_collateral_open_price,
         base_collateral_open_bid_ask,
@@ -43,18 +84,31 @@ pub fn execute_pending_position(
 
     let active_state = MtPositionActiveState {
         open_data: open_date,
-        asset_active_price: get_close_price(asset_price.as_ref(), &pending_position.base_data.side),
+        asset_active_price: asset_close_price,
         asset_active_bid_ask: asset_price.as_ref().clone(),
         quote_collateral_active_price: quote_collateral_close_price,
         quote_collateral_active_bid_ask: quote_collateral_close_bid_ask,
         profit: 0.0,
         swaps: crate::MtPositionSwaps::default(),
         topping_up: None,
-        is_margin_call_hit: false
+        is_margin_call_hit: false,
+        fees: crate::MtPositionFees::default(),
+        margin_call_notification: crate::MtMarginCallNotificationState::default(),
     };
 
-    return Ok(MtPosition {
+    let mut position = MtPosition {
         state: active_state,
-        base_data: pending_position.base_data,
-    });
+        base_data,
+    };
+
+    if let Some(fee_model) = fees_cache.get(
+        &position.base_data.asset_pair,
+        position.base_data.group_id.as_deref(),
+    ) {
+        position.state.fees = calculate_position_fees(&position, fee_model);
+    }
+
+    update_position_pl(&mut position)?;
+
+    return Ok(position);
 }
@@ -1,4 +1,7 @@
-use crate::{get_open_price, MtBidAsk, MtPosition, MtPositionPendingState, MtPositionSide};
+use crate::{
+    get_close_price, get_open_price, MtBidAsk, MtPosition, MtPositionPendingState,
+    MtPositionPendingStateType, MtPositionSide,
+};
 
 pub fn is_ready_to_execute_pending_position(
     position: &MtPosition<MtPositionPendingState>,
@@ -7,18 +10,92 @@ pub fn is_ready_to_execute_pending_position(
     let position_type = &position.state.position_type;
 
     match position_type {
-        crate::MtPositionPendingStateType::BuyStop => {
+        MtPositionPendingStateType::BuyStop => {
             get_open_price(current_bid_ask, &MtPositionSide::Buy) >= position.state.desire_price
         }
-        crate::MtPositionPendingStateType::BuyLimit => {
+        MtPositionPendingStateType::BuyLimit => {
             get_open_price(current_bid_ask, &MtPositionSide::Buy) <= position.state.desire_price
         }
-        crate::MtPositionPendingStateType::SellLimit => {
+        MtPositionPendingStateType::SellLimit => {
             get_open_price(current_bid_ask, &MtPositionSide::Sell) >= position.state.desire_price
         }
-        crate::MtPositionPendingStateType::SellStop => {
+        // A market-if-touched order triggers as soon as either side of the quote touches the
+        // level and is then executed at market, even if the price has moved away again.
+        MtPositionPendingStateType::BuyMarketIfTouched
+        | MtPositionPendingStateType::SellMarketIfTouched => {
+            position.state.is_triggered || is_pending_position_triggered(position, current_bid_ask)
+        }
+        MtPositionPendingStateType::SellStop => {
             get_open_price(current_bid_ask, &MtPositionSide::Sell) <= position.state.desire_price
         }
+        // Once triggered a stop-limit rests as a limit order: if the price gaps past the limit
+        // it is not executed until the price comes back to the limit.
+        MtPositionPendingStateType::BuyStopLimit | MtPositionPendingStateType::SellStopLimit => {
+            let Some(limit_price) = position.state.limit_price else {
+                return false;
+            };
+
+            if !position.state.is_triggered
+                && !is_pending_position_triggered(position, current_bid_ask)
+            {
+                return false;
+            }
+
+            match position.base_data.side {
+                MtPositionSide::Buy => {
+                    get_open_price(current_bid_ask, &MtPositionSide::Buy) <= limit_price
+                }
+                MtPositionSide::Sell => {
+                    get_open_price(current_bid_ask, &MtPositionSide::Sell) >= limit_price
+                }
+            }
+        }
+    }
+}
+
+pub fn update_pending_position_trigger(
+    position: &mut MtPosition<MtPositionPendingState>,
+    current_bid_ask: &MtBidAsk,
+) -> bool {
+    if position.state.is_triggered {
+        return false;
+    }
+
+    if !is_pending_position_triggered(position, current_bid_ask) {
+        return false;
+    }
+
+    position.state.is_triggered = true;
+
+    true
+}
+
+pub fn evaluate_pending_position(
+    position: &mut MtPosition<MtPositionPendingState>,
+    current_bid_ask: &MtBidAsk,
+) -> bool {
+    update_pending_position_trigger(position, current_bid_ask);
+    is_ready_to_execute_pending_position(position, current_bid_ask)
+}
+
+fn is_pending_position_triggered(
+    position: &MtPosition<MtPositionPendingState>,
+    current_bid_ask: &MtBidAsk,
+) -> bool {
+    match position.state.position_type {
+        MtPositionPendingStateType::BuyStopLimit => {
+            get_open_price(current_bid_ask, &MtPositionSide::Buy) >= position.state.desire_price
+        }
+        MtPositionPendingStateType::SellStopLimit => {
+            get_open_price(current_bid_ask, &MtPositionSide::Sell) <= position.state.desire_price
+        }
+        MtPositionPendingStateType::BuyMarketIfTouched => {
+            get_close_price(current_bid_ask, &MtPositionSide::Buy) <= position.state.desire_price
+        }
+        MtPositionPendingStateType::SellMarketIfTouched => {
+            get_close_price(current_bid_ask, &MtPositionSide::Sell) >= position.state.desire_price
+        }
+        _ => false,
     }
 }
 
@@ -27,8 +104,11 @@ mod tests {
     use rust_extensions::date_time::DateTimeAsMicroseconds;
 
     use crate::{
-        get_close_price, get_pending_position_type, is_ready_to_execute_pending_position, MtBidAsk,
-        MtPosition, MtPositionBaseData, MtPositionPendingState, MtPositionPendingStateType,
+        evaluate_pending_position, get_close_price, get_open_price,
+        get_pending_order_reference_price, get_pending_order_type, get_pending_position_type,
+        is_ready_to_execute_pending_position, update_pending_position_trigger, MtBidAsk,
+        MtPendingOrderKind, MtPosition, MtPositionBaseData, MtPositionPendingState,
+        MtPositionPendingStateType, MtValidationViolation, TestEntity,
     };
 
     #[test]
@@ -67,6 +147,7 @@ mod tests {
             margin_call_percent: None,
             topping_up_percent: None,
             metadata: None,
+            group_id: None,
         };
 
         let state: MtPositionPendingState = MtPositionPendingState {
@@ -76,6 +157,10 @@ mod tests {
                 desire_price,
                 &base_data.side,
             ),
+            limit_price: None,
+            is_triggered: false,
+            oco_group_id: None,
+            bracket: None,
         };
 
         let position = MtPosition {
@@ -155,6 +240,7 @@ mod tests {
             margin_call_percent: None,
             topping_up_percent: None,
             metadata: None,
+            group_id: None,
         };
 
         let state: MtPositionPendingState = MtPositionPendingState {
@@ -164,6 +250,10 @@ mod tests {
                 desire_price,
                 &base_data.side,
             ),
+            limit_price: None,
+            is_triggered: false,
+            oco_group_id: None,
+            bracket: None,
         };
 
         let position = MtPosition {
@@ -243,6 +333,7 @@ mod tests {
             margin_call_percent: None,
             topping_up_percent: None,
             metadata: None,
+            group_id: None,
         };
 
         let state: MtPositionPendingState = MtPositionPendingState {
@@ -252,6 +343,10 @@ mod tests {
                 desire_price,
                 &base_data.side,
             ),
+            limit_price: None,
+            is_triggered: false,
+            oco_group_id: None,
+            bracket: None,
         };
 
         let position = MtPosition {
@@ -331,6 +426,7 @@ mod tests {
             margin_call_percent: None,
             topping_up_percent: None,
             metadata: None,
+            group_id: None,
         };
 
         let state: MtPositionPendingState = MtPositionPendingState {
@@ -340,6 +436,10 @@ mod tests {
                 desire_price,
                 &base_data.side,
             ),
+            limit_price: None,
+            is_triggered: false,
+            oco_group_id: None,
+            bracket: None,
         };
 
         let position = MtPosition {
@@ -382,4 +482,293 @@ mod tests {
             is_ready_to_execute_pending_position(&position, &execute_bid_ask)
         );
     }
+
+    fn bid_ask(bid: f64, ask: f64) -> MtBidAsk {
+        MtBidAsk {
+            asset_pair: "EURUSD".to_string(),
+            bid,
+            ask,
+            base: "EUR".to_string(),
+            quote: "USD".to_string(),
+            date: DateTimeAsMicroseconds::now(),
+        }
+    }
+
+    fn create_position(
+        side: crate::MtPositionSide,
+        current_price: &MtBidAsk,
+        desire_price: f64,
+        order_kind: MtPendingOrderKind,
+    ) -> MtPosition<MtPositionPendingState> {
+        let mut base_data = MtPositionBaseData::generate_test_entity();
+        base_data.side = side;
+
+        let position_type = get_pending_order_type(
+            get_pending_order_reference_price(current_price, &base_data.side, &order_kind),
+            desire_price,
+            &base_data.side,
+            &order_kind,
+        )
+        .unwrap();
+
+        let limit_price = match order_kind {
+            MtPendingOrderKind::StopLimit { limit_price } => Some(limit_price),
+            _ => None,
+        };
+
+        MtPosition {
+            state: MtPositionPendingState {
+                desire_price,
+                position_type,
+                limit_price,
+                is_triggered: false,
+                oco_group_id: None,
+                bracket: None,
+            },
+            base_data,
+        }
+    }
+
+    #[test]
+    fn test_buy_stop_limit() {
+        let current_price = bid_ask(1.0588, 1.0688);
+
+        let mut position = create_position(
+            crate::MtPositionSide::Buy,
+            &current_price,
+            1.1000,
+            MtPendingOrderKind::StopLimit {
+                limit_price: 1.1050,
+            },
+        );
+
+        assert!(matches!(
+            position.state.position_type,
+            MtPositionPendingStateType::BuyStopLimit
+        ));
+        assert!(!is_ready_to_execute_pending_position(
+            &position,
+            &current_price
+        ));
+
+        let in_range = bid_ask(1.1010, 1.1020);
+        assert!(is_ready_to_execute_pending_position(&position, &in_range));
+
+        let gap = bid_ask(1.1090, 1.1100);
+        assert!(!is_ready_to_execute_pending_position(&position, &gap));
+        assert!(update_pending_position_trigger(&mut position, &gap));
+        assert!(!update_pending_position_trigger(&mut position, &gap));
+        assert!(!is_ready_to_execute_pending_position(&position, &gap));
+
+        let back_to_limit = bid_ask(1.0990, 1.1000);
+        assert!(is_ready_to_execute_pending_position(
+            &position,
+            &back_to_limit
+        ));
+
+        let mut position = create_position(
+            crate::MtPositionSide::Buy,
+            &current_price,
+            1.1000,
+            MtPendingOrderKind::StopLimit {
+                limit_price: 1.1050,
+            },
+        );
+
+        assert!(!evaluate_pending_position(&mut position, &gap));
+        assert!(position.state.is_triggered);
+        assert!(evaluate_pending_position(&mut position, &back_to_limit));
+    }
+
+    #[test]
+    fn test_sell_stop_limit() {
+        let current_price = bid_ask(1.0588, 1.0688);
+
+        let mut position = create_position(
+            crate::MtPositionSide::Sell,
+            &current_price,
+            1.0500,
+            MtPendingOrderKind::StopLimit {
+                limit_price: 1.0450,
+            },
+        );
+
+        assert!(matches!(
+            position.state.position_type,
+            MtPositionPendingStateType::SellStopLimit
+        ));
+        assert!(!is_ready_to_execute_pending_position(
+            &position,
+            &current_price
+        ));
+
+        let gap = bid_ask(1.0400, 1.0410);
+        assert!(update_pending_position_trigger(&mut position, &gap));
+        assert!(!is_ready_to_execute_pending_position(&position, &gap));
+
+        let back_to_limit = bid_ask(1.0460, 1.0470);
+        assert!(is_ready_to_execute_pending_position(
+            &position,
+            &back_to_limit
+        ));
+    }
+
+    #[test]
+    fn test_stop_limit_validation() {
+        let current_price = bid_ask(1.0588, 1.0688);
+
+        let result = get_pending_order_type(
+            1.0688,
+            1.0500,
+            &crate::MtPositionSide::Buy,
+            &MtPendingOrderKind::StopLimit {
+                limit_price: 1.0550,
+            },
+        );
+        assert!(matches!(
+            result,
+            Err(MtValidationViolation::PendingTriggerPriceInvalid { .. })
+        ));
+
+        let result = get_pending_order_type(
+            get_open_price(&current_price, &crate::MtPositionSide::Buy),
+            1.1000,
+            &crate::MtPositionSide::Buy,
+            &MtPendingOrderKind::StopLimit {
+                limit_price: 1.0900,
+            },
+        );
+        assert!(matches!(
+            result,
+            Err(MtValidationViolation::PendingLimitPriceInvalid { .. })
+        ));
+    }
+
+    #[test]
+    fn test_market_if_touched() {
+        let current_price = bid_ask(1.0588, 1.0688);
+
+        let buy = create_position(
+            crate::MtPositionSide::Buy,
+            &current_price,
+            1.0500,
+            MtPendingOrderKind::MarketIfTouched,
+        );
+        let sell = create_position(
+            crate::MtPositionSide::Sell,
+            &current_price,
+            1.0700,
+            MtPendingOrderKind::MarketIfTouched,
+        );
+
+        assert!(matches!(
+            buy.state.position_type,
+            MtPositionPendingStateType::BuyMarketIfTouched
+        ));
+        assert!(matches!(
+            sell.state.position_type,
+            MtPositionPendingStateType::SellMarketIfTouched
+        ));
+
+        assert!(!is_ready_to_execute_pending_position(&buy, &current_price));
+        assert!(!is_ready_to_execute_pending_position(&sell, &current_price));
+
+        let gap_down = bid_ask(1.0300, 1.0310);
+        assert!(is_ready_to_execute_pending_position(&buy, &gap_down));
+
+        let gap_up = bid_ask(1.0900, 1.0910);
+        assert!(is_ready_to_execute_pending_position(&sell, &gap_up));
+
+        // The bid touches the buy level while the ask is still above it, which is not enough
+        // for a buy limit at the same price.
+        let mut buy = buy;
+        let touched = bid_ask(1.0500, 1.0600);
+        assert!(evaluate_pending_position(&mut buy, &touched));
+
+        let moved_away = bid_ask(1.0700, 1.0800);
+        assert!(is_ready_to_execute_pending_position(&buy, &moved_away));
+
+        let buy_limit = create_position(
+            crate::MtPositionSide::Buy,
+            &current_price,
+            1.0500,
+            MtPendingOrderKind::Regular,
+        );
+        assert!(!is_ready_to_execute_pending_position(&buy_limit, &touched));
+
+        let result = get_pending_order_type(
+            1.0688,
+            1.0700,
+            &crate::MtPositionSide::Buy,
+            &MtPendingOrderKind::MarketIfTouched,
+        );
+        assert!(matches!(
+            result,
+            Err(MtValidationViolation::PendingTriggerPriceInvalid { .. })
+        ));
+    }
+
+    #[test]
+    fn test_market_if_touched_with_wide_spread() {
+        let current_price = bid_ask(1.0000, 1.1000);
+
+        // The level is below the ask but above the bid the order triggers on, so it would
+        // execute on the very first tick.
+        let result = get_pending_order_type(
+            get_pending_order_reference_price(
+                &current_price,
+                &crate::MtPositionSide::Buy,
+                &MtPendingOrderKind::MarketIfTouched,
+            ),
+            1.0500,
+            &crate::MtPositionSide::Buy,
+            &MtPendingOrderKind::MarketIfTouched,
+        );
+        assert!(matches!(
+            result,
+            Err(MtValidationViolation::PendingTriggerPriceInvalid { .. })
+        ));
+
+        let result = get_pending_order_type(
+            get_pending_order_reference_price(
+                &current_price,
+                &crate::MtPositionSide::Sell,
+                &MtPendingOrderKind::MarketIfTouched,
+            ),
+            1.0500,
+            &crate::MtPositionSide::Sell,
+            &MtPendingOrderKind::MarketIfTouched,
+        );
+        assert!(matches!(
+            result,
+            Err(MtValidationViolation::PendingTriggerPriceInvalid { .. })
+        ));
+
+        let mut buy = create_position(
+            crate::MtPositionSide::Buy,
+            &current_price,
+            0.9900,
+            MtPendingOrderKind::MarketIfTouched,
+        );
+        let mut sell = create_position(
+            crate::MtPositionSide::Sell,
+            &current_price,
+            1.1100,
+            MtPendingOrderKind::MarketIfTouched,
+        );
+
+        assert!(!evaluate_pending_position(&mut buy, &current_price));
+        assert!(!evaluate_pending_position(&mut sell, &current_price));
+        assert!(!buy.state.is_triggered);
+        assert!(!sell.state.is_triggered);
+
+        assert!(evaluate_pending_position(
+            &mut buy,
+            &bid_ask(0.9900, 1.0900)
+        ));
+        assert!(evaluate_pending_position(
+            &mut sell,
+            &bid_ask(1.0100, 1.1100)
+        ));
+    }
 }
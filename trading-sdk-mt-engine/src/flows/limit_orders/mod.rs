@@ -1,7 +1,13 @@
 mod create_pending_position;
 mod execute_pending_position;
 mod is_pending_position_ready_to_execute;
+mod modify_pending_position;
+mod cancel_pending_position;
+mod execute_oco_pending_position;
 
 pub use create_pending_position::*;
 pub use execute_pending_position::*;
-pub use is_pending_position_ready_to_execute::*;
\ No newline at end of file
+pub use is_pending_position_ready_to_execute::*;
+pub use modify_pending_position::*;
+pub use cancel_pending_position::*;
+pub use execute_oco_pending_position::*;
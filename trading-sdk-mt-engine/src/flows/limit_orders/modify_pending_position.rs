@@ -0,0 +1,200 @@
+use rust_extensions::date_time::DateTimeAsMicroseconds;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    get_pending_order_reference_price, get_pending_order_type, normalize_price,
+    validate_price_on_tick, validate_protection_levels, MtBidAskCache, MtEngineError, MtInstrument,
+    MtPosition, MtPositionPendingState, MtPositionPendingStateType, MtPositionProtection,
+};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MtPositionModifyPendingPriceCommand {
+    pub desire_price: f64,
+    pub process_id: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MtPendingPositionPriceUpdate {
+    pub position_id: String,
+    pub old_desire_price: f64,
+    pub new_desire_price: f64,
+    pub old_position_type: MtPositionPendingStateType,
+    pub new_position_type: MtPositionPendingStateType,
+    pub process_id: String,
+    pub date: DateTimeAsMicroseconds,
+}
+
+pub fn modify_pending_position_price(
+    position: &mut MtPosition<MtPositionPendingState>,
+    command: MtPositionModifyPendingPriceCommand,
+    prices_cache: &MtBidAskCache,
+    instrument: &MtInstrument,
+) -> Result<MtPendingPositionPriceUpdate, MtEngineError> {
+    let asset_price = prices_cache
+        .get_by_id(&position.base_data.asset_pair)
+        .ok_or_else(|| MtEngineError::NoLiquidity {
+            asset_pair: position.base_data.asset_pair.clone(),
+        })?;
+
+    let desire_price = normalize_price(instrument, command.desire_price);
+
+    validate_price_on_tick(instrument, Some(command.desire_price))
+        .and_then(|_| {
+            validate_protection_levels(
+                instrument,
+                &MtPositionProtection::from(&position.base_data),
+                &position.base_data.side,
+                desire_price,
+                0.0,
+            )
+        })
+        .map_err(|violation| MtEngineError::validation_failed(&position.base_data.id, violation))?;
+
+    let order_kind = position.state.get_order_kind();
+    let current_price = get_pending_order_reference_price(
+        asset_price.as_ref(),
+        &position.base_data.side,
+        &order_kind,
+    );
+    let new_position_type = get_pending_order_type(
+        current_price,
+        desire_price,
+        &position.base_data.side,
+        &order_kind,
+    )
+    .map_err(|violation| MtEngineError::validation_failed(&position.base_data.id, violation))?;
+
+    let now = DateTimeAsMicroseconds::now();
+    let old_desire_price = position.state.desire_price;
+    let old_position_type =
+        std::mem::replace(&mut position.state.position_type, new_position_type.clone());
+
+    position.state.desire_price = desire_price;
+    position.state.is_triggered = false;
+
+    position.base_data.last_update_process_id = command.process_id.clone();
+    position.base_data.last_update_date = now;
+
+    Ok(MtPendingPositionPriceUpdate {
+        position_id: position.base_data.id.clone(),
+        old_desire_price,
+        new_desire_price: desire_price,
+        old_position_type,
+        new_position_type,
+        process_id: command.process_id,
+        date: now,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        modify_pending_position_price, MtBidAsk, MtBidAskCache, MtEngineError, MtInstrument,
+        MtOffTickPolicy, MtPosition, MtPositionBaseData, MtPositionModifyPendingPriceCommand,
+        MtPositionPendingState, MtPositionPendingStateType, MtValidationViolation, TestEntity,
+    };
+
+    #[test]
+    fn test_modify_pending_price_recalculates_type() {
+        let prices_cache = MtBidAskCache::from_iter(vec![MtBidAsk::generate_test_entity()]);
+        let mut position = MtPosition {
+            state: MtPositionPendingState::generate_test_entity(),
+            base_data: MtPositionBaseData::generate_test_entity(),
+        };
+        position.base_data.asset_pair = "BASEQUOTE".to_string();
+
+        let update = modify_pending_position_price(
+            &mut position,
+            MtPositionModifyPendingPriceCommand {
+                desire_price: 30.0,
+                process_id: "modify_process".to_string(),
+            },
+            &prices_cache,
+            &MtInstrument::generate_test_entity(),
+        )
+        .unwrap();
+
+        assert_eq!(update.old_desire_price, 20.0);
+        assert_eq!(update.new_desire_price, 30.0);
+        assert!(matches!(
+            update.old_position_type,
+            MtPositionPendingStateType::BuyLimit
+        ));
+        assert!(matches!(
+            position.state.position_type,
+            MtPositionPendingStateType::BuyStop
+        ));
+        assert_eq!(position.state.desire_price, 30.0);
+        assert_eq!(position.base_data.last_update_process_id, "modify_process");
+    }
+
+    #[test]
+    fn test_modify_pending_price_validates_protection() {
+        let prices_cache = MtBidAskCache::from_iter(vec![MtBidAsk::generate_test_entity()]);
+        let mut position = MtPosition {
+            state: MtPositionPendingState::generate_test_entity(),
+            base_data: MtPositionBaseData::generate_test_entity(),
+        };
+        position.base_data.asset_pair = "BASEQUOTE".to_string();
+        position.base_data.sl_price = Some(18.0);
+
+        let result = modify_pending_position_price(
+            &mut position,
+            MtPositionModifyPendingPriceCommand {
+                desire_price: 17.0,
+                process_id: "modify_process".to_string(),
+            },
+            &prices_cache,
+            &MtInstrument::generate_test_entity(),
+        );
+
+        assert!(matches!(
+            result,
+            Err(MtEngineError::ValidationFailed {
+                violation: MtValidationViolation::StopLossPriceInvalid { .. },
+                ..
+            })
+        ));
+        assert_eq!(position.state.desire_price, 20.0);
+    }
+
+    #[test]
+    fn test_modify_pending_price_off_tick_policy() {
+        let prices_cache = MtBidAskCache::from_iter(vec![MtBidAsk::generate_test_entity()]);
+        let mut position = MtPosition {
+            state: MtPositionPendingState::generate_test_entity(),
+            base_data: MtPositionBaseData::generate_test_entity(),
+        };
+        position.base_data.asset_pair = "BASEQUOTE".to_string();
+
+        let mut instrument = MtInstrument::generate_test_entity();
+        instrument.tick_size = Some(0.5);
+
+        let command = MtPositionModifyPendingPriceCommand {
+            desire_price: 29.7,
+            process_id: "modify_process".to_string(),
+        };
+
+        let result = modify_pending_position_price(
+            &mut position,
+            command.clone(),
+            &prices_cache,
+            &instrument,
+        );
+        assert!(matches!(
+            result,
+            Err(MtEngineError::ValidationFailed {
+                violation: MtValidationViolation::PriceNotOnTick { .. },
+                ..
+            })
+        ));
+
+        instrument.off_tick_policy = MtOffTickPolicy::Round;
+        let update =
+            modify_pending_position_price(&mut position, command, &prices_cache, &instrument)
+                .unwrap();
+
+        assert_eq!(update.new_desire_price, 29.5);
+        assert_eq!(position.state.desire_price, 29.5);
+    }
+}
@@ -3,9 +3,27 @@ mod calculations;
 mod closed_positions;
 mod utils;
 mod limit_orders;
+mod validation;
+mod protection;
+mod netting;
+mod balance;
+mod exposure;
+mod routing;
+mod hedging;
+mod sessions;
+mod statistics;
 
 pub use active_positions::*;
 pub use calculations::*;
 pub use closed_positions::*;
 pub use utils::*;
 pub use limit_orders::*;
+pub use validation::*;
+pub use protection::*;
+pub use netting::*;
+pub use balance::*;
+pub use exposure::*;
+pub use routing::*;
+pub use hedging::*;
+pub use sessions::*;
+pub use statistics::*;
@@ -0,0 +1,3 @@
+mod open_netting_position;
+
+pub use open_netting_position::*;
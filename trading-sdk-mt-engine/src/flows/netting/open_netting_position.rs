@@ -0,0 +1,680 @@
+use rust_extensions::date_time::DateTimeAsMicroseconds;
+use trading_sdk_core::EngineCacheQueryBuilder;
+
+use crate::{
+    build_active_position, convert_position_to_closed, get_position_base_volume,
+    make_active_position, update_active_position_rate, update_position_pl, validate_asset_pair,
+    validate_instrument_status, validate_invest_amount, validate_price_age,
+    validate_price_deviation, validate_price_on_tick, validate_trading_session,
+    ActivePositionsCache, MtAccountMode, MtBidAskCache, MtEngineError, MtFeeModelsCache,
+    MtInstrumentsCache, MtPosition, MtPositionActiveState, MtPositionCloseReason,
+    MtPositionClosedState, MtPositionOpenCommand, MtValidationViolation,
+};
+
+pub struct MtNettingResult {
+    pub active_position: Option<MtPosition<MtPositionActiveState>>,
+    pub closed_positions: Vec<MtPosition<MtPositionClosedState>>,
+}
+
+pub fn open_position_by_account_mode(
+    account_mode: &MtAccountMode,
+    active_cache: &mut ActivePositionsCache,
+    open_command: MtPositionOpenCommand,
+    prices_cache: &MtBidAskCache,
+    instruments_cache: &MtInstrumentsCache,
+    fees_cache: &MtFeeModelsCache,
+) -> Result<MtNettingResult, MtEngineError> {
+    match account_mode {
+        MtAccountMode::Hedging => {
+            let position =
+                make_active_position(open_command, prices_cache, instruments_cache, fees_cache)?;
+            active_cache.0.add_position(position.clone());
+
+            Ok(MtNettingResult {
+                active_position: Some(position),
+                closed_positions: vec![],
+            })
+        }
+        MtAccountMode::Netting => open_netting_position(
+            active_cache,
+            open_command,
+            prices_cache,
+            instruments_cache,
+            fees_cache,
+        ),
+    }
+}
+
+pub fn open_netting_position(
+    active_cache: &mut ActivePositionsCache,
+    open_command: MtPositionOpenCommand,
+    prices_cache: &MtBidAskCache,
+    instruments_cache: &MtInstrumentsCache,
+    fees_cache: &MtFeeModelsCache,
+) -> Result<MtNettingResult, MtEngineError> {
+    let query = EngineCacheQueryBuilder::new()
+        .with_account(&open_command.account_id)
+        .with_base(&open_command.base)
+        .with_quote(&open_command.quote);
+
+    let existing = active_cache
+        .0
+        .query_positions(query)
+        .into_iter()
+        .find(|position| position.base_data.asset_pair == open_command.asset_pair)
+        .cloned();
+
+    let Some(existing) = existing else {
+        let new_position =
+            make_active_position(open_command, prices_cache, instruments_cache, fees_cache)?;
+        active_cache.0.add_position(new_position.clone());
+
+        return Ok(MtNettingResult {
+            active_position: Some(new_position),
+            closed_positions: vec![],
+        });
+    };
+
+    let existing_id = existing.base_data.id.clone();
+    let process_id = open_command.process_id.clone();
+
+    let result = if existing.base_data.side == open_command.side {
+        let new_position =
+            make_active_position(open_command, prices_cache, instruments_cache, fees_cache)?;
+
+        MtNettingResult {
+            active_position: Some(increase_netting_position(
+                existing,
+                new_position,
+                process_id,
+            )?),
+            closed_positions: vec![],
+        }
+    } else {
+        let order = make_offset_order(
+            &existing,
+            open_command,
+            prices_cache,
+            instruments_cache,
+            fees_cache,
+        )?;
+
+        offset_netting_position(existing, order, process_id)?
+    };
+
+    active_cache.0.remove_position(&existing_id);
+
+    if let Some(active_position) = &result.active_position {
+        active_cache.0.add_position(active_position.clone());
+    }
+
+    Ok(result)
+}
+
+fn increase_netting_position(
+    mut existing: MtPosition<MtPositionActiveState>,
+    new_position: MtPosition<MtPositionActiveState>,
+    process_id: String,
+) -> Result<MtPosition<MtPositionActiveState>, MtEngineError> {
+    let existing_base_volume = get_position_base_volume(&existing);
+    let new_base_volume = get_position_base_volume(&new_position);
+    let base_volume = existing_base_volume + new_base_volume;
+
+    let existing_volume = existing.base_data.invest_amount * existing.base_data.leverage;
+    let new_volume = new_position.base_data.invest_amount * new_position.base_data.leverage;
+    let volume = existing_volume + new_volume;
+
+    existing.state.open_data.asset_open_price = (existing.state.open_data.asset_open_price
+        * existing_base_volume
+        + new_position.state.open_data.asset_open_price * new_base_volume)
+        / base_volume;
+
+    existing.base_data.invest_amount += new_position.base_data.invest_amount;
+    existing.base_data.leverage = volume / existing.base_data.invest_amount;
+
+    if existing.base_data.base != existing.base_data.collateral
+        && existing.base_data.quote != existing.base_data.collateral
+    {
+        // Cross instruments derive their base volume from the base/collateral rate, so the
+        // rate is re-derived to keep the merged base volume equal to the sum of both legs.
+        let inverted_base = match &existing.state.open_data.base_collateral_open_bid_ask {
+            Some(bid_ask) => bid_ask.quote != existing.base_data.collateral,
+            None => false,
+        };
+
+        existing.state.open_data.base_collateral_open_price = match inverted_base {
+            true => base_volume / volume,
+            false => volume / base_volume,
+        };
+    }
+
+    if let Some(topping_up) = new_position.state.topping_up {
+        existing.state.topping_up = Some(existing.state.topping_up.unwrap_or(0.0) + topping_up);
+    }
+
+    existing.state.fees.open_fee += new_position.state.fees.open_fee;
+    existing.state.fees.close_fee += new_position.state.fees.close_fee;
+
+    existing.state.asset_active_price = new_position.state.asset_active_price;
+    existing.state.asset_active_bid_ask = new_position.state.asset_active_bid_ask;
+    existing.state.quote_collateral_active_price = new_position.state.quote_collateral_active_price;
+    existing.state.quote_collateral_active_bid_ask =
+        new_position.state.quote_collateral_active_bid_ask;
+
+    existing.base_data.last_update_process_id = process_id;
+    existing.base_data.last_update_date = new_position.base_data.last_update_date;
+
+    update_position_pl(&mut existing)?;
+
+    Ok(existing)
+}
+
+fn make_offset_order(
+    existing: &MtPosition<MtPositionActiveState>,
+    open_command: MtPositionOpenCommand,
+    prices_cache: &MtBidAskCache,
+    instruments_cache: &MtInstrumentsCache,
+    fees_cache: &MtFeeModelsCache,
+) -> Result<MtPosition<MtPositionActiveState>, MtEngineError> {
+    let instrument = instruments_cache
+        .get_by_id(&open_command.asset_pair)
+        .ok_or_else(|| MtEngineError::InstrumentNotFound {
+            asset_pair: open_command.asset_pair.clone(),
+        })?;
+
+    let asset_price = prices_cache
+        .get_by_id(&open_command.asset_pair)
+        .ok_or_else(|| MtEngineError::NoLiquidity {
+            asset_pair: open_command.asset_pair.clone(),
+        })?;
+
+    let validation_result = validate_asset_pair(
+        &instrument,
+        &asset_price,
+        &open_command.base,
+        &open_command.quote,
+    )
+    .and_then(|_| match instrument.status.is_evaluation_allowed() {
+        true => Ok(()),
+        false => Err(MtValidationViolation::InstrumentStatusRestricted {
+            status: instrument.status.clone(),
+        }),
+    })
+    .and_then(|_| validate_trading_session(&instrument, DateTimeAsMicroseconds::now()));
+
+    if let Err(violation) = validation_result {
+        return Err(MtEngineError::validation_failed(
+            &open_command.id,
+            violation,
+        ));
+    }
+
+    validate_price_age(&instrument, &asset_price, DateTimeAsMicroseconds::now())?;
+
+    validate_price_deviation(
+        &instrument,
+        &asset_price,
+        &open_command.side,
+        open_command.expected_price,
+        open_command.max_deviation,
+    )?;
+
+    let tp_price = open_command.tp_price;
+    let sl_price = open_command.sl_price;
+
+    let order = build_active_position(
+        open_command,
+        &instrument,
+        &asset_price,
+        prices_cache,
+        fees_cache,
+    )?;
+
+    let existing_base_volume = get_position_base_volume(existing);
+    let order_base_volume = get_position_base_volume(&order);
+
+    if order_base_volume > existing_base_volume
+        && !is_same_base_volume(existing_base_volume, order_base_volume)
+    {
+        // Only the part of the order left after the offset opens new exposure.
+        let reversed_invest_amount =
+            order.base_data.invest_amount * (1.0 - existing_base_volume / order_base_volume);
+
+        let validation_result = validate_instrument_status(&instrument)
+            .and_then(|_| {
+                validate_invest_amount(
+                    &instrument,
+                    reversed_invest_amount,
+                    order.base_data.leverage,
+                )
+            })
+            .and_then(|_| validate_price_on_tick(&instrument, tp_price))
+            .and_then(|_| validate_price_on_tick(&instrument, sl_price));
+
+        if let Err(violation) = validation_result {
+            return Err(MtEngineError::validation_failed(
+                &order.base_data.id,
+                violation,
+            ));
+        }
+    }
+
+    Ok(order)
+}
+
+fn is_same_base_volume(existing_base_volume: f64, order_base_volume: f64) -> bool {
+    (order_base_volume - existing_base_volume).abs() <= existing_base_volume * 1e-9
+}
+
+fn offset_netting_position(
+    mut existing: MtPosition<MtPositionActiveState>,
+    order: MtPosition<MtPositionActiveState>,
+    process_id: String,
+) -> Result<MtNettingResult, MtEngineError> {
+    update_active_position_rate(&mut existing, &order.state.open_data.asset_open_bid_ask);
+
+    if let Some(bid_ask) = &order.state.quote_collateral_active_bid_ask {
+        update_active_position_rate(&mut existing, bid_ask);
+    }
+
+    update_position_pl(&mut existing)?;
+
+    let existing_base_volume = get_position_base_volume(&existing);
+    let order_base_volume = get_position_base_volume(&order);
+    let is_full_offset = is_same_base_volume(existing_base_volume, order_base_volume);
+
+    if !is_full_offset && order_base_volume < existing_base_volume {
+        let fraction = order_base_volume / existing_base_volume;
+        let (mut slice, mut remaining) = split_position(existing, fraction)?;
+
+        slice.base_data.id = order.base_data.id;
+        charge_order_fee(&mut slice, order.state.fees.open_fee)?;
+
+        remaining.base_data.last_update_process_id = process_id.clone();
+        remaining.base_data.last_update_date = order.base_data.last_update_date;
+
+        return Ok(MtNettingResult {
+            active_position: Some(remaining),
+            closed_positions: vec![convert_position_to_closed(
+                slice,
+                MtPositionCloseReason::Netting,
+                process_id,
+            )],
+        });
+    }
+
+    let offset_fraction = match is_full_offset {
+        true => 1.0,
+        false => existing_base_volume / order_base_volume,
+    };
+
+    charge_order_fee(&mut existing, order.state.fees.open_fee * offset_fraction)?;
+
+    let closed_positions = vec![convert_position_to_closed(
+        existing,
+        MtPositionCloseReason::Netting,
+        process_id,
+    )];
+
+    if is_full_offset {
+        return Ok(MtNettingResult {
+            active_position: None,
+            closed_positions,
+        });
+    }
+
+    let (_, reversed) = split_position(order, offset_fraction)?;
+
+    Ok(MtNettingResult {
+        active_position: Some(reversed),
+        closed_positions,
+    })
+}
+
+fn charge_order_fee(
+    position: &mut MtPosition<MtPositionActiveState>,
+    fee: f64,
+) -> Result<(), MtEngineError> {
+    position.state.fees.close_fee += fee;
+    update_position_pl(position)
+}
+
+fn split_position(
+    position: MtPosition<MtPositionActiveState>,
+    fraction: f64,
+) -> Result<
+    (
+        MtPosition<MtPositionActiveState>,
+        MtPosition<MtPositionActiveState>,
+    ),
+    MtEngineError,
+> {
+    let mut slice = position.clone();
+    let mut remaining = position;
+
+    scale_position(&mut slice, fraction)?;
+    scale_position(&mut remaining, 1.0 - fraction)?;
+
+    Ok((slice, remaining))
+}
+
+fn scale_position(
+    position: &mut MtPosition<MtPositionActiveState>,
+    fraction: f64,
+) -> Result<(), MtEngineError> {
+    position.base_data.invest_amount *= fraction;
+    position.state.topping_up = position.state.topping_up.map(|x| x * fraction);
+
+    for swap in position.state.swaps.swaps.iter_mut() {
+        swap.amount *= fraction;
+    }
+    position.state.swaps.total *= fraction;
+
+    position.state.fees.open_fee *= fraction;
+    position.state.fees.close_fee *= fraction;
+
+    update_position_pl(position)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        open_netting_position, ActivePositionsCache, MtBidAsk, MtBidAskCache, MtEngineError,
+        MtFeeCalculationType, MtFeeChargeType, MtFeeModel, MtFeeModelsCache, MtInstrument,
+        MtInstrumentStatus, MtInstrumentsCache, MtPositionCloseReason, MtPositionOpenCommand,
+        MtPositionSide, TestEntity,
+    };
+
+    fn create_prices_cache(bid: f64, ask: f64) -> MtBidAskCache {
+        MtBidAskCache::from_iter(vec![MtBidAsk {
+            bid,
+            ask,
+            ..MtBidAsk::generate_test_entity()
+        }])
+    }
+
+    #[test]
+    fn test_increase_position() {
+        let mut cache = ActivePositionsCache::new();
+        let instruments_cache =
+            MtInstrumentsCache::from_iter(vec![MtInstrument::generate_test_entity()]);
+        let fees_cache = MtFeeModelsCache::new();
+
+        open_netting_position(
+            &mut cache,
+            MtPositionOpenCommand {
+                id: "first".to_string(),
+                ..MtPositionOpenCommand::generate_test_entity()
+            },
+            &create_prices_cache(0.9, 1.0),
+            &instruments_cache,
+            &fees_cache,
+        )
+        .unwrap();
+
+        let result = open_netting_position(
+            &mut cache,
+            MtPositionOpenCommand {
+                id: "second".to_string(),
+                ..MtPositionOpenCommand::generate_test_entity()
+            },
+            &create_prices_cache(1.9, 2.0),
+            &instruments_cache,
+            &fees_cache,
+        )
+        .unwrap();
+
+        let position = result.active_position.unwrap();
+
+        assert!(result.closed_positions.is_empty());
+        assert_eq!(position.base_data.id, "first");
+        assert_eq!(position.base_data.invest_amount, 200.0);
+        assert_eq!(
+            format!("{:.4}", position.state.open_data.asset_open_price),
+            "1.3333"
+        );
+        assert_eq!(format!("{:.2}", position.state.profit), "850.00");
+        assert_eq!(cache.0.positions.len(), 1);
+    }
+
+    #[test]
+    fn test_reduce_close_and_reverse() {
+        let mut cache = ActivePositionsCache::new();
+        let instruments_cache =
+            MtInstrumentsCache::from_iter(vec![MtInstrument::generate_test_entity()]);
+        let fees_cache = MtFeeModelsCache::new();
+        let prices_cache = create_prices_cache(1.0, 1.0);
+
+        open_netting_position(
+            &mut cache,
+            MtPositionOpenCommand {
+                id: "first".to_string(),
+                ..MtPositionOpenCommand::generate_test_entity()
+            },
+            &prices_cache,
+            &instruments_cache,
+            &fees_cache,
+        )
+        .unwrap();
+
+        let result = open_netting_position(
+            &mut cache,
+            MtPositionOpenCommand {
+                id: "reduce".to_string(),
+                side: MtPositionSide::Sell,
+                invest_amount: 40.0,
+                ..MtPositionOpenCommand::generate_test_entity()
+            },
+            &prices_cache,
+            &instruments_cache,
+            &fees_cache,
+        )
+        .unwrap();
+
+        assert_eq!(
+            result.active_position.unwrap().base_data.invest_amount,
+            60.0
+        );
+        assert_eq!(result.closed_positions.len(), 1);
+        assert_eq!(result.closed_positions[0].base_data.id, "reduce");
+        assert_eq!(result.closed_positions[0].base_data.invest_amount, 40.0);
+        assert!(matches!(
+            result.closed_positions[0].state.close_reason,
+            MtPositionCloseReason::Netting
+        ));
+
+        let result = open_netting_position(
+            &mut cache,
+            MtPositionOpenCommand {
+                id: "reverse".to_string(),
+                side: MtPositionSide::Sell,
+                ..MtPositionOpenCommand::generate_test_entity()
+            },
+            &prices_cache,
+            &instruments_cache,
+            &fees_cache,
+        )
+        .unwrap();
+
+        let reversed = result.active_position.unwrap();
+        assert_eq!(reversed.base_data.id, "reverse");
+        assert!(matches!(reversed.base_data.side, MtPositionSide::Sell));
+        assert_eq!(format!("{:.2}", reversed.base_data.invest_amount), "40.00");
+        assert_eq!(result.closed_positions[0].base_data.id, "first");
+        assert!(cache.0.get_by_id("first").is_none());
+        assert!(cache.0.get_by_id("reverse").is_some());
+
+        let result = open_netting_position(
+            &mut cache,
+            MtPositionOpenCommand {
+                id: "close".to_string(),
+                invest_amount: 40.0,
+                ..MtPositionOpenCommand::generate_test_entity()
+            },
+            &prices_cache,
+            &instruments_cache,
+            &fees_cache,
+        )
+        .unwrap();
+
+        assert!(result.active_position.is_none());
+        assert_eq!(result.closed_positions.len(), 1);
+        assert!(cache.0.positions.is_empty());
+    }
+
+    #[test]
+    fn test_increase_with_different_leverage() {
+        let mut cache = ActivePositionsCache::new();
+        let instruments_cache =
+            MtInstrumentsCache::from_iter(vec![MtInstrument::generate_test_entity()]);
+        let fees_cache = MtFeeModelsCache::new();
+        let prices_cache = create_prices_cache(1.0, 1.0);
+
+        open_netting_position(
+            &mut cache,
+            MtPositionOpenCommand {
+                id: "first".to_string(),
+                ..MtPositionOpenCommand::generate_test_entity()
+            },
+            &prices_cache,
+            &instruments_cache,
+            &fees_cache,
+        )
+        .unwrap();
+
+        let mut command = MtPositionOpenCommand {
+            id: "second".to_string(),
+            ..MtPositionOpenCommand::generate_test_entity()
+        };
+        command.leverage = 20.0;
+
+        let position = open_netting_position(
+            &mut cache,
+            command,
+            &prices_cache,
+            &instruments_cache,
+            &fees_cache,
+        )
+        .unwrap()
+        .active_position
+        .unwrap();
+
+        assert_eq!(position.base_data.invest_amount, 200.0);
+        assert_eq!(position.base_data.leverage, 15.0);
+    }
+
+    #[test]
+    fn test_reduce_at_order_price() {
+        let mut cache = ActivePositionsCache::new();
+        let mut instrument = MtInstrument::generate_test_entity();
+        instrument.min_invest_amount = Some(50.0);
+        let instruments_cache = MtInstrumentsCache::from_iter(vec![instrument]);
+
+        let mut fees_cache = MtFeeModelsCache::new();
+        fees_cache.handle_new(
+            "BASEQUOTE",
+            None,
+            MtFeeModel {
+                calculation_type: MtFeeCalculationType::Fixed { amount: 2.0 },
+                charge_type: MtFeeChargeType::Open,
+            },
+        );
+
+        open_netting_position(
+            &mut cache,
+            MtPositionOpenCommand {
+                id: "first".to_string(),
+                ..MtPositionOpenCommand::generate_test_entity()
+            },
+            &create_prices_cache(0.9, 1.0),
+            &instruments_cache,
+            &fees_cache,
+        )
+        .unwrap();
+
+        let result = open_netting_position(
+            &mut cache,
+            MtPositionOpenCommand {
+                id: "reduce".to_string(),
+                side: MtPositionSide::Sell,
+                invest_amount: 40.0,
+                ..MtPositionOpenCommand::generate_test_entity()
+            },
+            &create_prices_cache(2.0, 2.1),
+            &instruments_cache,
+            &fees_cache,
+        )
+        .unwrap();
+
+        let closed = &result.closed_positions[0];
+        assert_eq!(closed.state.asset_close_price, 2.0);
+        assert_eq!(closed.state.asset_close_bid_ask.bid, 2.0);
+        assert_eq!(format!("{:.2}", closed.base_data.invest_amount), "20.00");
+        assert_eq!(
+            format!("{:.2}", closed.state.active_state.fees.open_fee),
+            "0.40"
+        );
+        assert_eq!(closed.state.active_state.fees.close_fee, 2.0);
+        assert_eq!(format!("{:.2}", closed.state.active_state.profit), "197.60");
+
+        let remaining = result.active_position.unwrap();
+        assert_eq!(format!("{:.2}", remaining.base_data.invest_amount), "80.00");
+        assert_eq!(remaining.state.asset_active_price, 2.0);
+    }
+
+    #[test]
+    fn test_close_only_allows_reduce() {
+        let mut cache = ActivePositionsCache::new();
+        let fees_cache = MtFeeModelsCache::new();
+        let prices_cache = create_prices_cache(1.0, 1.0);
+
+        open_netting_position(
+            &mut cache,
+            MtPositionOpenCommand {
+                id: "first".to_string(),
+                ..MtPositionOpenCommand::generate_test_entity()
+            },
+            &prices_cache,
+            &MtInstrumentsCache::from_iter(vec![MtInstrument::generate_test_entity()]),
+            &fees_cache,
+        )
+        .unwrap();
+
+        let mut instrument = MtInstrument::generate_test_entity();
+        instrument.status = MtInstrumentStatus::CloseOnly;
+        let instruments_cache = MtInstrumentsCache::from_iter(vec![instrument]);
+
+        let result = open_netting_position(
+            &mut cache,
+            MtPositionOpenCommand {
+                id: "reverse".to_string(),
+                side: MtPositionSide::Sell,
+                invest_amount: 150.0,
+                ..MtPositionOpenCommand::generate_test_entity()
+            },
+            &prices_cache,
+            &instruments_cache,
+            &fees_cache,
+        );
+        assert!(matches!(
+            result,
+            Err(MtEngineError::ValidationFailed { .. })
+        ));
+        assert!(cache.0.get_by_id("first").is_some());
+
+        let result = open_netting_position(
+            &mut cache,
+            MtPositionOpenCommand {
+                id: "reduce".to_string(),
+                side: MtPositionSide::Sell,
+                invest_amount: 40.0,
+                ..MtPositionOpenCommand::generate_test_entity()
+            },
+            &prices_cache,
+            &instruments_cache,
+            &fees_cache,
+        )
+        .unwrap();
+        assert_eq!(result.closed_positions.len(), 1);
+    }
+}
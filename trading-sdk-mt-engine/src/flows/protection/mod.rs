@@ -0,0 +1,3 @@
+mod modify_position_protection;
+
+pub use modify_position_protection::*;
@@ -0,0 +1,224 @@
+use rust_extensions::date_time::DateTimeAsMicroseconds;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    normalize_optional_price, sanitize_sl_tp, validate_bracket_protection, validate_price_on_tick,
+    validate_protection_levels, MtEngineError, MtInstrument, MtPosition, MtPositionActiveState,
+    MtPositionBaseData, MtPositionPendingState, MtPositionProtection,
+};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MtPositionModifyProtectionCommand {
+    pub tp_profit: Option<f64>,
+    pub tp_price: Option<f64>,
+    pub sl_profit: Option<f64>,
+    pub sl_price: Option<f64>,
+    pub process_id: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MtPositionProtectionUpdate {
+    pub position_id: String,
+    pub old_protection: MtPositionProtection,
+    pub new_protection: MtPositionProtection,
+    pub process_id: String,
+    pub date: DateTimeAsMicroseconds,
+}
+
+pub fn modify_active_position_protection(
+    position: &mut MtPosition<MtPositionActiveState>,
+    command: MtPositionModifyProtectionCommand,
+    instrument: &MtInstrument,
+) -> Result<MtPositionProtectionUpdate, MtEngineError> {
+    let reference_price = position.state.asset_active_price;
+    let current_profit = position.state.profit;
+
+    modify_protection(
+        &mut position.base_data,
+        command,
+        instrument,
+        reference_price,
+        current_profit,
+    )
+}
+
+pub fn modify_pending_position_protection(
+    position: &mut MtPosition<MtPositionPendingState>,
+    command: MtPositionModifyProtectionCommand,
+    instrument: &MtInstrument,
+) -> Result<MtPositionProtectionUpdate, MtEngineError> {
+    let reference_price = position.state.desire_price;
+
+    if let Some(bracket) = &position.state.bracket {
+        let protection = MtPositionProtection {
+            tp_profit: command.tp_profit,
+            tp_price: command.tp_price,
+            sl_profit: command.sl_profit,
+            sl_price: command.sl_price,
+        };
+
+        validate_bracket_protection(bracket, &protection).map_err(|violation| {
+            MtEngineError::validation_failed(&position.base_data.id, violation)
+        })?;
+    }
+
+    modify_protection(
+        &mut position.base_data,
+        command,
+        instrument,
+        reference_price,
+        0.0,
+    )
+}
+
+fn modify_protection(
+    base_data: &mut MtPositionBaseData,
+    command: MtPositionModifyProtectionCommand,
+    instrument: &MtInstrument,
+    reference_price: f64,
+    current_profit: f64,
+) -> Result<MtPositionProtectionUpdate, MtEngineError> {
+    let old_protection = MtPositionProtection::from(&*base_data);
+
+    validate_price_on_tick(instrument, command.tp_price)
+        .and_then(|_| validate_price_on_tick(instrument, command.sl_price))
+        .map_err(|violation| MtEngineError::validation_failed(&base_data.id, violation))?;
+
+    let mut updated_base_data = base_data.clone();
+    updated_base_data.tp_profit = command.tp_profit;
+    updated_base_data.tp_price = normalize_optional_price(instrument, command.tp_price);
+    updated_base_data.sl_profit = command.sl_profit;
+    updated_base_data.sl_price = normalize_optional_price(instrument, command.sl_price);
+    sanitize_sl_tp(&mut updated_base_data);
+
+    let new_protection = MtPositionProtection::from(&updated_base_data);
+
+    validate_protection_levels(
+        instrument,
+        &new_protection,
+        &base_data.side,
+        reference_price,
+        current_profit,
+    )
+    .map_err(|violation| MtEngineError::validation_failed(&base_data.id, violation))?;
+
+    let now = DateTimeAsMicroseconds::now();
+
+    new_protection.apply_to(base_data);
+    base_data.last_update_process_id = command.process_id.clone();
+    base_data.last_update_date = now;
+
+    Ok(MtPositionProtectionUpdate {
+        position_id: base_data.id.clone(),
+        old_protection,
+        new_protection,
+        process_id: command.process_id,
+        date: now,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        modify_active_position_protection, MtEngineError, MtInstrument, MtOffTickPolicy,
+        MtPosition, MtPositionModifyProtectionCommand, MtValidationViolation, TestEntity,
+    };
+
+    #[test]
+    fn test_modify_active_position_protection() {
+        let mut position = MtPosition::generate_test_entity();
+        position.base_data.sl_price = Some(20.0);
+
+        let command = MtPositionModifyProtectionCommand {
+            tp_profit: None,
+            tp_price: Some(30.0),
+            sl_profit: Some(50.0),
+            sl_price: Some(22.0),
+            process_id: "modify_process".to_string(),
+        };
+
+        let update = modify_active_position_protection(
+            &mut position,
+            command,
+            &MtInstrument::generate_test_entity(),
+        )
+        .unwrap();
+
+        assert_eq!(update.old_protection.sl_price, Some(20.0));
+        assert_eq!(update.new_protection.sl_price, Some(22.0));
+        assert_eq!(update.new_protection.sl_profit, Some(-50.0));
+        assert_eq!(position.base_data.sl_price, Some(22.0));
+        assert_eq!(position.base_data.sl_profit, Some(-50.0));
+        assert_eq!(position.base_data.tp_price, Some(30.0));
+        assert_eq!(position.base_data.last_update_process_id, "modify_process");
+    }
+
+    #[test]
+    fn test_modify_active_position_protection_rejects_immediate_sl() {
+        let mut position = MtPosition::generate_test_entity();
+        position.base_data.sl_price = Some(20.0);
+
+        let command = MtPositionModifyProtectionCommand {
+            tp_profit: None,
+            tp_price: None,
+            sl_profit: None,
+            sl_price: Some(26.0),
+            process_id: "modify_process".to_string(),
+        };
+
+        let result = modify_active_position_protection(
+            &mut position,
+            command,
+            &MtInstrument::generate_test_entity(),
+        );
+
+        assert!(matches!(
+            result,
+            Err(MtEngineError::ValidationFailed {
+                violation: MtValidationViolation::StopLossPriceInvalid { .. },
+                ..
+            })
+        ));
+        assert_eq!(position.base_data.sl_price, Some(20.0));
+        assert_eq!(
+            position.base_data.last_update_process_id,
+            "last_update_process_id"
+        );
+    }
+
+    #[test]
+    fn test_modify_active_position_protection_off_tick() {
+        let mut position = MtPosition::generate_test_entity();
+        position.base_data.sl_price = Some(20.0);
+
+        let mut instrument = MtInstrument::generate_test_entity();
+        instrument.tick_size = Some(0.5);
+
+        let command = MtPositionModifyProtectionCommand {
+            tp_profit: None,
+            tp_price: None,
+            sl_profit: None,
+            sl_price: Some(21.3),
+            process_id: "modify_process".to_string(),
+        };
+
+        let result = modify_active_position_protection(&mut position, command.clone(), &instrument);
+
+        assert!(matches!(
+            result,
+            Err(MtEngineError::ValidationFailed {
+                violation: MtValidationViolation::PriceNotOnTick { .. },
+                ..
+            })
+        ));
+        assert_eq!(position.base_data.sl_price, Some(20.0));
+
+        instrument.off_tick_policy = MtOffTickPolicy::Round;
+
+        let update =
+            modify_active_position_protection(&mut position, command, &instrument).unwrap();
+
+        assert_eq!(update.new_protection.sl_price, Some(21.5));
+        assert_eq!(position.base_data.sl_price, Some(21.5));
+    }
+}
@@ -0,0 +1,7 @@
+mod mt_liquidity_provider;
+mod mt_routing_policy;
+mod route_position_execution;
+
+pub use mt_liquidity_provider::*;
+pub use mt_routing_policy::*;
+pub use route_position_execution::*;
@@ -0,0 +1,39 @@
+use std::future::Future;
+
+use rust_extensions::date_time::DateTimeAsMicroseconds;
+use serde::{Deserialize, Serialize};
+
+use crate::{MtEngineError, MtPositionSide};
+
+pub trait MtLiquidityProvider {
+    fn execute_order(
+        &self,
+        order: MtLiquidityProviderOrder,
+    ) -> impl Future<Output = Result<MtLiquidityProviderFill, MtEngineError>> + Send;
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MtLiquidityProviderOrder {
+    pub position_id: String,
+    pub account_id: String,
+    pub asset_pair: String,
+    pub side: MtPositionSide,
+    pub volume: f64,
+    pub requested_price: f64,
+    pub price_limit: Option<MtLiquidityProviderPriceLimit>,
+    pub process_id: String,
+}
+
+/// Lets the provider reject an order before filling it when the fill would
+/// move against the client by more than `max_deviation`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MtLiquidityProviderPriceLimit {
+    pub expected_price: f64,
+    pub max_deviation: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MtLiquidityProviderFill {
+    pub price: f64,
+    pub date: DateTimeAsMicroseconds,
+}
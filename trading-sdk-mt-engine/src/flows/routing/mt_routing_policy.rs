@@ -0,0 +1,64 @@
+use std::collections::HashSet;
+
+use serde::{Deserialize, Serialize};
+
+use crate::MtPositionSide;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum MtExecutionRoute {
+    Internal = 0,
+    External = 1,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum MtRoutingOrderType {
+    Open = 0,
+    Close = 1,
+    PendingExecution = 2,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MtRoutingRequest {
+    pub order_type: MtRoutingOrderType,
+    pub account_id: String,
+    pub group_id: Option<String>,
+    pub asset_pair: String,
+    pub side: MtPositionSide,
+    pub volume: f64,
+}
+
+pub trait MtRoutingPolicy {
+    fn get_route(&self, request: &MtRoutingRequest) -> MtExecutionRoute;
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct MtRoutingRules {
+    pub external_accounts: HashSet<String>,
+    pub external_groups: HashSet<String>,
+    pub external_instruments: HashSet<String>,
+    pub external_min_volume: Option<f64>,
+}
+
+impl MtRoutingPolicy for MtRoutingRules {
+    fn get_route(&self, request: &MtRoutingRequest) -> MtExecutionRoute {
+        let is_group_external = match &request.group_id {
+            Some(group_id) => self.external_groups.contains(group_id),
+            None => false,
+        };
+
+        let is_volume_external = match self.external_min_volume {
+            Some(min_volume) => request.volume >= min_volume,
+            None => false,
+        };
+
+        if is_group_external
+            || is_volume_external
+            || self.external_accounts.contains(&request.account_id)
+            || self.external_instruments.contains(&request.asset_pair)
+        {
+            return MtExecutionRoute::External;
+        }
+
+        MtExecutionRoute::Internal
+    }
+}
@@ -0,0 +1,535 @@
+use std::sync::Arc;
+
+use crate::{
+    calculate_position_fees, close_position_with_policy, convert_position_to_closed,
+    execute_pending_position, get_position_base_volume, make_active_position,
+    normalize_optional_price, normalize_price, update_position_pl,
+    validate_execution_price_deviation, MtBidAskCache, MtCloseExecutionPolicy, MtEngineError,
+    MtExecutionRoute, MtFeeModelsCache, MtInstrument, MtInstrumentsCache, MtLiquidityProvider,
+    MtLiquidityProviderOrder, MtLiquidityProviderPriceLimit, MtPosition, MtPositionActiveState,
+    MtPositionCloseReason, MtPositionClosedState, MtPositionOpenCommand, MtPositionPendingState,
+    MtPositionSide, MtRoutingOrderType, MtRoutingPolicy, MtRoutingRequest,
+};
+
+#[derive(Debug, Clone)]
+pub struct MtRoutedExecution<T> {
+    pub position: T,
+    pub route: MtExecutionRoute,
+}
+
+pub async fn open_position_routed(
+    open_command: MtPositionOpenCommand,
+    prices_cache: &MtBidAskCache,
+    instruments_cache: &MtInstrumentsCache,
+    fees_cache: &MtFeeModelsCache,
+    routing_policy: &impl MtRoutingPolicy,
+    liquidity_provider: &impl MtLiquidityProvider,
+) -> Result<MtRoutedExecution<MtPosition<MtPositionActiveState>>, MtEngineError> {
+    let price_limit =
+        open_command
+            .expected_price
+            .map(|expected_price| MtLiquidityProviderPriceLimit {
+                expected_price,
+                max_deviation: open_command.max_deviation.unwrap_or(0.0),
+            });
+    let position = make_active_position(open_command, prices_cache, instruments_cache, fees_cache)?;
+    let instrument = get_instrument(&position, instruments_cache)?;
+
+    route_open(
+        position,
+        MtRoutingOrderType::Open,
+        price_limit,
+        &instrument,
+        fees_cache,
+        routing_policy,
+        liquidity_provider,
+    )
+    .await
+}
+
+pub async fn execute_pending_position_routed(
+    pending_position: MtPosition<MtPositionPendingState>,
+    prices_cache: &MtBidAskCache,
+    instruments_cache: &MtInstrumentsCache,
+    fees_cache: &MtFeeModelsCache,
+    routing_policy: &impl MtRoutingPolicy,
+    liquidity_provider: &impl MtLiquidityProvider,
+    process_id: String,
+) -> Result<MtRoutedExecution<MtPosition<MtPositionActiveState>>, MtEngineError> {
+    let position = execute_pending_position(
+        pending_position,
+        prices_cache,
+        instruments_cache,
+        fees_cache,
+        process_id,
+    )?;
+    let instrument = get_instrument(&position, instruments_cache)?;
+
+    route_open(
+        position,
+        MtRoutingOrderType::PendingExecution,
+        None,
+        &instrument,
+        fees_cache,
+        routing_policy,
+        liquidity_provider,
+    )
+    .await
+}
+
+pub async fn close_position_routed(
+    position: &MtPosition<MtPositionActiveState>,
+    instrument: &MtInstrument,
+    close_reason: MtPositionCloseReason,
+    close_policy: &MtCloseExecutionPolicy,
+    routing_policy: &impl MtRoutingPolicy,
+    liquidity_provider: &impl MtLiquidityProvider,
+    process_id: String,
+) -> Result<MtRoutedExecution<MtPosition<MtPositionClosedState>>, MtEngineError> {
+    let close_side = match position.base_data.side {
+        MtPositionSide::Buy => MtPositionSide::Sell,
+        MtPositionSide::Sell => MtPositionSide::Buy,
+    };
+
+    let request = get_routing_request(position, MtRoutingOrderType::Close, close_side.clone());
+    let route = routing_policy.get_route(&request);
+
+    if route == MtExecutionRoute::Internal {
+        return Ok(MtRoutedExecution {
+            position: close_position_with_policy(
+                position.clone(),
+                close_reason,
+                close_policy,
+                process_id,
+            )?,
+            route,
+        });
+    }
+
+    let fill = liquidity_provider
+        .execute_order(MtLiquidityProviderOrder {
+            position_id: position.base_data.id.clone(),
+            account_id: position.base_data.account_id.clone(),
+            asset_pair: position.base_data.asset_pair.clone(),
+            side: close_side,
+            volume: request.volume,
+            requested_price: position.state.asset_active_price,
+            price_limit: None,
+            process_id: process_id.clone(),
+        })
+        .await?;
+
+    let fill_price = normalize_price(instrument, fill.price);
+    let price_shift = fill_price - position.state.asset_active_price;
+
+    let mut position = position.clone();
+    position.state.asset_active_price = fill_price;
+    position.state.asset_active_bid_ask.bid += price_shift;
+    position.state.asset_active_bid_ask.ask += price_shift;
+    update_position_pl(&mut position)?;
+
+    Ok(MtRoutedExecution {
+        position: convert_position_to_closed(position, close_reason, process_id),
+        route,
+    })
+}
+
+pub fn apply_open_fill_price(
+    position: &mut MtPosition<MtPositionActiveState>,
+    instrument: &MtInstrument,
+    fill_price: f64,
+    fees_cache: &MtFeeModelsCache,
+) -> Result<(), MtEngineError> {
+    let fill_price = normalize_price(instrument, fill_price);
+    let price_shift = fill_price - position.state.open_data.asset_open_price;
+
+    position.state.open_data.asset_open_price = fill_price;
+    position.state.open_data.asset_open_bid_ask.bid += price_shift;
+    position.state.open_data.asset_open_bid_ask.ask += price_shift;
+
+    if let Some(bracket) = position
+        .state
+        .open_data
+        .pending_state
+        .as_ref()
+        .and_then(|pending_state| pending_state.bracket.as_ref())
+    {
+        bracket.apply_to(&mut position.base_data, fill_price);
+        position.base_data.sl_price =
+            normalize_optional_price(instrument, position.base_data.sl_price);
+        position.base_data.tp_price =
+            normalize_optional_price(instrument, position.base_data.tp_price);
+    }
+
+    if let Some(fee_model) = fees_cache.get(
+        &position.base_data.asset_pair,
+        position.base_data.group_id.as_deref(),
+    ) {
+        position.state.fees = calculate_position_fees(position, fee_model);
+    }
+
+    update_position_pl(position)
+}
+
+async fn route_open(
+    mut position: MtPosition<MtPositionActiveState>,
+    order_type: MtRoutingOrderType,
+    price_limit: Option<MtLiquidityProviderPriceLimit>,
+    instrument: &MtInstrument,
+    fees_cache: &MtFeeModelsCache,
+    routing_policy: &impl MtRoutingPolicy,
+    liquidity_provider: &impl MtLiquidityProvider,
+) -> Result<MtRoutedExecution<MtPosition<MtPositionActiveState>>, MtEngineError> {
+    let request = get_routing_request(&position, order_type, position.base_data.side.clone());
+    let route = routing_policy.get_route(&request);
+
+    if route == MtExecutionRoute::External {
+        let order = MtLiquidityProviderOrder {
+            position_id: position.base_data.id.clone(),
+            account_id: position.base_data.account_id.clone(),
+            asset_pair: position.base_data.asset_pair.clone(),
+            side: position.base_data.side.clone(),
+            volume: request.volume,
+            requested_price: position.state.open_data.asset_open_price,
+            price_limit: price_limit.clone(),
+            process_id: position.state.open_data.open_process_id.clone(),
+        };
+        let fill = liquidity_provider.execute_order(order.clone()).await?;
+
+        apply_open_fill_price(&mut position, instrument, fill.price, fees_cache)?;
+
+        if let Some(price_limit) = price_limit {
+            // The provider checks the raw fill, normalization can still push it past the limit.
+            if let Err(err) = validate_execution_price_deviation(
+                instrument,
+                &position.base_data.asset_pair,
+                &position.base_data.side,
+                position.state.open_data.asset_open_price,
+                Some(price_limit.expected_price),
+                Some(price_limit.max_deviation),
+            ) {
+                compensate_open_fill(order, fill.price, liquidity_provider).await?;
+                return Err(err);
+            }
+        }
+    }
+
+    Ok(MtRoutedExecution { position, route })
+}
+
+async fn compensate_open_fill(
+    order: MtLiquidityProviderOrder,
+    fill_price: f64,
+    liquidity_provider: &impl MtLiquidityProvider,
+) -> Result<(), MtEngineError> {
+    let side = match order.side {
+        MtPositionSide::Buy => MtPositionSide::Sell,
+        MtPositionSide::Sell => MtPositionSide::Buy,
+    };
+
+    liquidity_provider
+        .execute_order(MtLiquidityProviderOrder {
+            side,
+            requested_price: fill_price,
+            price_limit: None,
+            ..order
+        })
+        .await?;
+
+    Ok(())
+}
+
+fn get_instrument(
+    position: &MtPosition<MtPositionActiveState>,
+    instruments_cache: &MtInstrumentsCache,
+) -> Result<Arc<MtInstrument>, MtEngineError> {
+    instruments_cache
+        .get_by_id(&position.base_data.asset_pair)
+        .ok_or_else(|| MtEngineError::InstrumentNotFound {
+            asset_pair: position.base_data.asset_pair.clone(),
+        })
+}
+
+fn get_routing_request(
+    position: &MtPosition<MtPositionActiveState>,
+    order_type: MtRoutingOrderType,
+    side: MtPositionSide,
+) -> MtRoutingRequest {
+    MtRoutingRequest {
+        order_type,
+        account_id: position.base_data.account_id.clone(),
+        group_id: position.base_data.group_id.clone(),
+        asset_pair: position.base_data.asset_pair.clone(),
+        side,
+        volume: get_position_base_volume(position),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_extensions::date_time::DateTimeAsMicroseconds;
+
+    use crate::{
+        close_position_routed, execute_pending_position_routed, open_position_routed, MtBidAsk,
+        MtBidAskCache, MtCloseExecutionPolicy, MtEngineError, MtExecutionRoute,
+        MtFakeLiquidityProvider, MtFeeModelsCache, MtInstrument, MtInstrumentsCache,
+        MtPendingBracket, MtPosition, MtPositionBaseData, MtPositionCloseReason,
+        MtPositionOpenCommand, MtPositionPendingState, MtPositionSide, MtRoutingRules, TestEntity,
+    };
+
+    fn create_caches() -> (MtBidAskCache, MtInstrumentsCache) {
+        let prices_cache = MtBidAskCache::from_iter(vec![MtBidAsk {
+            asset_pair: "BASEQUOTE".to_string(),
+            bid: 24.0,
+            ask: 25.0,
+            base: "base".to_string(),
+            quote: "quote".to_string(),
+            date: DateTimeAsMicroseconds::now(),
+        }]);
+        let instruments_cache =
+            MtInstrumentsCache::from_iter(vec![MtInstrument::generate_test_entity()]);
+
+        (prices_cache, instruments_cache)
+    }
+
+    fn create_rules() -> MtRoutingRules {
+        let mut rules = MtRoutingRules::default();
+        rules.external_accounts.insert("a_book".to_string());
+        rules
+    }
+
+    #[tokio::test]
+    async fn test_internal_open() {
+        let (prices_cache, instruments_cache) = create_caches();
+        let liquidity_provider = MtFakeLiquidityProvider::new(0.5);
+
+        let result = open_position_routed(
+            MtPositionOpenCommand {
+                account_id: "b_book".to_string(),
+                ..MtPositionOpenCommand::generate_test_entity()
+            },
+            &prices_cache,
+            &instruments_cache,
+            &MtFeeModelsCache::new(),
+            &create_rules(),
+            &liquidity_provider,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(result.route, MtExecutionRoute::Internal);
+        assert_eq!(result.position.state.open_data.asset_open_price, 25.0);
+        assert!(liquidity_provider.get_orders().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_external_open_and_close() {
+        let (prices_cache, instruments_cache) = create_caches();
+        let liquidity_provider = MtFakeLiquidityProvider::new(0.5);
+        let rules = create_rules();
+
+        let result = open_position_routed(
+            MtPositionOpenCommand {
+                account_id: "a_book".to_string(),
+                ..MtPositionOpenCommand::generate_test_entity()
+            },
+            &prices_cache,
+            &instruments_cache,
+            &MtFeeModelsCache::new(),
+            &rules,
+            &liquidity_provider,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(result.route, MtExecutionRoute::External);
+        assert_eq!(result.position.state.open_data.asset_open_price, 25.5);
+        assert_eq!(
+            result.position.state.profit,
+            (24.0 - 25.5) * (1000.0 / 25.5)
+        );
+
+        let closed = close_position_routed(
+            &result.position,
+            &MtInstrument::generate_test_entity(),
+            MtPositionCloseReason::ClientCommand,
+            &MtCloseExecutionPolicy::Market,
+            &rules,
+            &liquidity_provider,
+            "close".to_string(),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(closed.route, MtExecutionRoute::External);
+        assert_eq!(closed.position.state.active_state.asset_active_price, 23.5);
+        assert_eq!(closed.position.state.asset_close_bid_ask.bid, 23.5);
+        assert_eq!(
+            closed.position.state.active_state.profit,
+            (23.5 - 25.5) * (1000.0 / 25.5)
+        );
+
+        let orders = liquidity_provider.get_orders();
+        assert_eq!(orders.len(), 2);
+        assert_eq!(orders[0].volume, 40.0);
+        assert_eq!(orders[1].side, MtPositionSide::Sell);
+    }
+
+    #[tokio::test]
+    async fn test_external_fill_normalized_and_checked() {
+        let (prices_cache, _) = create_caches();
+        let mut instrument = MtInstrument::generate_test_entity();
+        instrument.tick_size = Some(0.25);
+        let instruments_cache = MtInstrumentsCache::from_iter(vec![instrument]);
+        let liquidity_provider = MtFakeLiquidityProvider::new(0.3);
+
+        let result = open_position_routed(
+            MtPositionOpenCommand {
+                account_id: "a_book".to_string(),
+                ..MtPositionOpenCommand::generate_test_entity()
+            },
+            &prices_cache,
+            &instruments_cache,
+            &MtFeeModelsCache::new(),
+            &create_rules(),
+            &liquidity_provider,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(result.position.state.open_data.asset_open_price, 25.25);
+        assert_eq!(
+            result.position.state.open_data.asset_open_bid_ask.ask,
+            25.25
+        );
+
+        let mut command = MtPositionOpenCommand {
+            account_id: "a_book".to_string(),
+            ..MtPositionOpenCommand::generate_test_entity()
+        };
+        command.expected_price = Some(25.0);
+        command.max_deviation = Some(0.1);
+
+        let result = open_position_routed(
+            command,
+            &prices_cache,
+            &instruments_cache,
+            &MtFeeModelsCache::new(),
+            &create_rules(),
+            &liquidity_provider,
+        )
+        .await;
+
+        let Err(MtEngineError::Requote {
+            expected_price,
+            actual_price,
+            ..
+        }) = result
+        else {
+            panic!("Expected requote");
+        };
+
+        assert_eq!(expected_price, 25.0);
+        assert_eq!(actual_price, 25.3);
+        assert_eq!(liquidity_provider.get_orders().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_external_fill_compensated_after_normalization() {
+        let (prices_cache, _) = create_caches();
+        let mut instrument = MtInstrument::generate_test_entity();
+        instrument.tick_size = Some(0.5);
+        let instruments_cache = MtInstrumentsCache::from_iter(vec![instrument]);
+        let liquidity_provider = MtFakeLiquidityProvider::new(0.3);
+
+        let mut command = MtPositionOpenCommand {
+            account_id: "a_book".to_string(),
+            ..MtPositionOpenCommand::generate_test_entity()
+        };
+        command.expected_price = Some(25.0);
+        command.max_deviation = Some(0.35);
+
+        let result = open_position_routed(
+            command,
+            &prices_cache,
+            &instruments_cache,
+            &MtFeeModelsCache::new(),
+            &create_rules(),
+            &liquidity_provider,
+        )
+        .await;
+
+        let Err(MtEngineError::Requote { actual_price, .. }) = result else {
+            panic!("Expected requote");
+        };
+
+        assert_eq!(actual_price, 25.5);
+
+        let orders = liquidity_provider.get_orders();
+        assert_eq!(orders.len(), 2);
+        assert_eq!(orders[0].side, MtPositionSide::Buy);
+        assert_eq!(orders[1].side, MtPositionSide::Sell);
+        assert_eq!(orders[1].volume, orders[0].volume);
+        assert_eq!(orders[1].requested_price, 25.0 + 0.3);
+    }
+
+    #[tokio::test]
+    async fn test_external_pending_execution_moves_bracket() {
+        let (prices_cache, instruments_cache) = create_caches();
+        let liquidity_provider = MtFakeLiquidityProvider::new(0.5);
+
+        let mut position = MtPosition {
+            state: MtPositionPendingState::generate_test_entity(),
+            base_data: MtPositionBaseData::generate_test_entity(),
+        };
+        position.base_data.account_id = "a_book".to_string();
+        position.base_data.asset_pair = "BASEQUOTE".to_string();
+        position.base_data.collateral = "quote".to_string();
+        position.state.bracket = Some(MtPendingBracket {
+            sl_distance: Some(2.0),
+            tp_distance: Some(2.0),
+        });
+
+        let result = execute_pending_position_routed(
+            position,
+            &prices_cache,
+            &instruments_cache,
+            &MtFeeModelsCache::new(),
+            &create_rules(),
+            &liquidity_provider,
+            "process".to_string(),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(result.route, MtExecutionRoute::External);
+        assert_eq!(result.position.state.open_data.asset_open_price, 25.5);
+        assert_eq!(result.position.base_data.sl_price, Some(23.5));
+        assert_eq!(result.position.base_data.tp_price, Some(27.5));
+    }
+
+    #[tokio::test]
+    async fn test_external_open_rejected() {
+        let (prices_cache, instruments_cache) = create_caches();
+        let mut liquidity_provider = MtFakeLiquidityProvider::new(0.0);
+        liquidity_provider.is_rejecting = true;
+
+        let result = open_position_routed(
+            MtPositionOpenCommand {
+                account_id: "a_book".to_string(),
+                ..MtPositionOpenCommand::generate_test_entity()
+            },
+            &prices_cache,
+            &instruments_cache,
+            &MtFeeModelsCache::new(),
+            &create_rules(),
+            &liquidity_provider,
+        )
+        .await;
+
+        assert!(matches!(
+            result,
+            Err(MtEngineError::LiquidityProviderRejected { .. })
+        ));
+    }
+}
@@ -0,0 +1,5 @@
+mod session_evaluation;
+mod trading_session;
+
+pub use session_evaluation::*;
+pub use trading_session::*;
@@ -0,0 +1,158 @@
+use crate::{
+    evaluate_pending_position, get_close_reason, MtBidAsk, MtCloseExecutionPolicy, MtEngineError,
+    MtInstrument, MtPosition, MtPositionActiveState, MtPositionCloseReason, MtPositionPendingState,
+    MtSessionTick,
+};
+
+pub fn get_close_reason_in_session(
+    position: &MtPosition<MtPositionActiveState>,
+    instrument: &MtInstrument,
+    session_tick: &MtSessionTick,
+) -> Result<Option<MtPositionCloseReason>, MtEngineError> {
+    if !session_tick.is_open {
+        return Ok(None);
+    }
+
+    get_close_reason(position, instrument)
+}
+
+pub fn evaluate_pending_position_in_session(
+    position: &mut MtPosition<MtPositionPendingState>,
+    current_bid_ask: &MtBidAsk,
+    session_tick: &MtSessionTick,
+) -> bool {
+    if !session_tick.is_open {
+        return false;
+    }
+
+    evaluate_pending_position(position, current_bid_ask)
+}
+
+pub fn get_session_close_execution_policy(
+    policy: &MtCloseExecutionPolicy,
+    session_tick: &MtSessionTick,
+) -> MtCloseExecutionPolicy {
+    let is_gap = session_tick.is_reopened && session_tick.gap.is_some_and(|gap| gap != 0.0);
+
+    match (is_gap, policy) {
+        (_, MtCloseExecutionPolicy::GuaranteedLevel) => MtCloseExecutionPolicy::GuaranteedLevel,
+        (true, _) => MtCloseExecutionPolicy::Market,
+        (false, _) => policy.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_extensions::date_time::DateTimeAsMicroseconds;
+
+    use crate::{
+        evaluate_pending_position_in_session, get_close_reason, get_close_reason_in_session,
+        get_session_close_execution_policy, MtBidAsk, MtCloseExecutionPolicy, MtInstrument,
+        MtPosition, MtPositionActiveState, MtPositionBaseData, MtPositionCloseReason,
+        MtPositionPendingState, MtSessionTick, MtTradingSchedule, MtTradingSessionWindow,
+        TestEntity,
+    };
+
+    fn create_tick(is_open: bool, is_reopened: bool, gap: Option<f64>) -> MtSessionTick {
+        MtSessionTick {
+            asset_pair: "BASEQUOTE".to_string(),
+            is_open,
+            is_reopened,
+            gap,
+        }
+    }
+
+    #[test]
+    fn test_evaluation_suspended_while_closed() {
+        let mut position: MtPosition<MtPositionActiveState> = MtPosition::generate_test_entity();
+        position.base_data.sl_price = Some(position.state.asset_active_price + 1.0);
+        let instrument = MtInstrument::generate_test_entity();
+
+        assert!(get_close_reason_in_session(
+            &position,
+            &instrument,
+            &create_tick(false, false, None)
+        )
+        .unwrap()
+        .is_none());
+        assert!(matches!(
+            get_close_reason_in_session(
+                &position,
+                &instrument,
+                &create_tick(true, true, Some(-1.0))
+            )
+            .unwrap(),
+            Some(MtPositionCloseReason::StopLoss)
+        ));
+
+        let mut pending_position = MtPosition {
+            state: MtPositionPendingState::generate_test_entity(),
+            base_data: MtPositionBaseData::generate_test_entity(),
+        };
+        pending_position.state.desire_price = 30.0;
+        let bid_ask = MtBidAsk::generate_test_entity();
+
+        assert!(!evaluate_pending_position_in_session(
+            &mut pending_position,
+            &bid_ask,
+            &create_tick(false, false, None)
+        ));
+        assert!(evaluate_pending_position_in_session(
+            &mut pending_position,
+            &bid_ask,
+            &create_tick(true, true, Some(5.0))
+        ));
+    }
+
+    #[test]
+    fn test_gap_uses_market_execution() {
+        let policy = MtCloseExecutionPolicy::TakeProfitLevelCappedByMarket;
+
+        assert!(matches!(
+            get_session_close_execution_policy(&policy, &create_tick(true, true, Some(1.5))),
+            MtCloseExecutionPolicy::Market
+        ));
+        assert!(matches!(
+            get_session_close_execution_policy(&policy, &create_tick(true, false, None)),
+            MtCloseExecutionPolicy::TakeProfitLevelCappedByMarket
+        ));
+        assert!(matches!(
+            get_session_close_execution_policy(
+                &MtCloseExecutionPolicy::GuaranteedLevel,
+                &create_tick(true, true, Some(1.5))
+            ),
+            MtCloseExecutionPolicy::GuaranteedLevel
+        ));
+    }
+
+    #[test]
+    fn test_close_reason_waits_for_session() {
+        let mut position: MtPosition<MtPositionActiveState> = MtPosition::generate_test_entity();
+        position.base_data.sl_price = Some(position.state.asset_active_price + 1.0);
+
+        let mut instrument = MtInstrument::generate_test_entity();
+        instrument.trading_schedule = Some(MtTradingSchedule {
+            timezone: "UTC".to_string(),
+            windows: (0..5)
+                .map(|weekday| MtTradingSessionWindow {
+                    weekday,
+                    open_minute: 0,
+                    close_minute: 24 * 60,
+                })
+                .collect(),
+            daily_breaks: vec![],
+            holidays: vec![],
+        });
+
+        // Saturday 2024-01-06 12:00 UTC
+        position.state.asset_active_bid_ask.date = DateTimeAsMicroseconds::new(1704542400000000);
+        assert!(get_close_reason(&position, &instrument).unwrap().is_none());
+
+        // Monday 2024-01-08 12:00 UTC
+        position.state.asset_active_bid_ask.date = DateTimeAsMicroseconds::new(1704715200000000);
+        assert!(matches!(
+            get_close_reason(&position, &instrument).unwrap(),
+            Some(MtPositionCloseReason::StopLoss)
+        ));
+    }
+}
@@ -0,0 +1,139 @@
+use chrono::{DateTime, Datelike, Timelike};
+use chrono_tz::Tz;
+use rust_extensions::date_time::DateTimeAsMicroseconds;
+
+use crate::{MtInstrument, MtTradingSchedule, MtValidationViolation};
+
+pub fn is_trading_session_open(schedule: &MtTradingSchedule, date: DateTimeAsMicroseconds) -> bool {
+    let Ok(timezone) = schedule.timezone.parse::<Tz>() else {
+        return false;
+    };
+
+    let Some(utc_date) = DateTime::from_timestamp_micros(date.unix_microseconds) else {
+        return false;
+    };
+
+    let local_date = utc_date.with_timezone(&timezone);
+
+    let is_holiday = schedule.holidays.iter().any(|holiday| {
+        holiday.year == local_date.year()
+            && holiday.month == local_date.month()
+            && holiday.day == local_date.day()
+    });
+
+    if is_holiday {
+        return false;
+    }
+
+    let weekday = local_date.weekday().num_days_from_monday();
+    let minute = local_date.hour() * 60 + local_date.minute();
+
+    let is_break = schedule
+        .daily_breaks
+        .iter()
+        .any(|daily_break| minute >= daily_break.start_minute && minute < daily_break.end_minute);
+
+    if is_break {
+        return false;
+    }
+
+    schedule.windows.iter().any(|window| {
+        window.weekday == weekday && minute >= window.open_minute && minute < window.close_minute
+    })
+}
+
+pub fn is_instrument_session_open(instrument: &MtInstrument, date: DateTimeAsMicroseconds) -> bool {
+    match &instrument.trading_schedule {
+        Some(schedule) => is_trading_session_open(schedule, date),
+        None => true,
+    }
+}
+
+pub fn validate_trading_session(
+    instrument: &MtInstrument,
+    date: DateTimeAsMicroseconds,
+) -> Result<(), MtValidationViolation> {
+    if !is_instrument_session_open(instrument, date) {
+        return Err(MtValidationViolation::MarketClosed);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_extensions::date_time::DateTimeAsMicroseconds;
+
+    use crate::{
+        is_trading_session_open, MtTradingHoliday, MtTradingSchedule, MtTradingSessionBreak,
+        MtTradingSessionWindow,
+    };
+
+    fn create_schedule() -> MtTradingSchedule {
+        MtTradingSchedule {
+            timezone: "America/New_York".to_string(),
+            windows: (0..5)
+                .map(|weekday| MtTradingSessionWindow {
+                    weekday,
+                    open_minute: 9 * 60 + 30,
+                    close_minute: 16 * 60,
+                })
+                .collect(),
+            daily_breaks: vec![MtTradingSessionBreak {
+                start_minute: 12 * 60,
+                end_minute: 12 * 60 + 30,
+            }],
+            holidays: vec![MtTradingHoliday {
+                year: 2026,
+                month: 12,
+                day: 25,
+            }],
+        }
+    }
+
+    fn date(value: &str) -> DateTimeAsMicroseconds {
+        let date = chrono::DateTime::parse_from_rfc3339(value).unwrap();
+        DateTimeAsMicroseconds::new(date.timestamp_micros())
+    }
+
+    #[test]
+    fn test_trading_session() {
+        let schedule = create_schedule();
+
+        assert!(is_trading_session_open(
+            &schedule,
+            date("2026-10-19T14:00:00Z")
+        ));
+        assert!(!is_trading_session_open(
+            &schedule,
+            date("2026-10-19T13:00:00Z")
+        ));
+        assert!(!is_trading_session_open(
+            &schedule,
+            date("2026-10-19T16:10:00Z")
+        ));
+        assert!(!is_trading_session_open(
+            &schedule,
+            date("2026-10-18T15:00:00Z")
+        ));
+        assert!(!is_trading_session_open(
+            &schedule,
+            date("2026-12-25T15:00:00Z")
+        ));
+        assert!(is_trading_session_open(
+            &schedule,
+            date("2026-12-24T15:00:00Z")
+        ));
+    }
+
+    #[test]
+    fn test_invalid_timezone_is_closed() {
+        let mut schedule = create_schedule();
+        schedule.timezone = "Mars/Olympus".to_string();
+
+        assert!(!is_trading_session_open(
+            &schedule,
+            date("2026-10-19T14:00:00Z")
+        ));
+    }
+}
@@ -0,0 +1,3 @@
+mod trading_statistics;
+
+pub use trading_statistics::*;
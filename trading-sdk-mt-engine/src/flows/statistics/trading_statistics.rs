@@ -0,0 +1,297 @@
+use std::collections::HashMap;
+
+use rust_extensions::date_time::DateTimeAsMicroseconds;
+
+use crate::{
+    MtEquityPoint, MtPosition, MtPositionActiveState, MtPositionCloseReason, MtPositionClosedState,
+    MtTradingStatistics, MtTradingStatisticsReport,
+};
+
+#[derive(Default)]
+struct MtTradingStatisticsAccumulator {
+    statistics: MtTradingStatistics,
+    cumulative_profit: f64,
+    peak_profit: f64,
+    total_holding_microseconds: i64,
+}
+
+impl MtTradingStatisticsAccumulator {
+    fn add(&mut self, position: &MtPosition<MtPositionClosedState>) {
+        let active_state = &position.state.active_state;
+        let profit = active_state.profit;
+        let statistics = &mut self.statistics;
+
+        statistics.total_positions += 1;
+
+        if profit > 0.0 {
+            statistics.winning_positions += 1;
+            statistics.gross_profit += profit;
+        } else if profit < 0.0 {
+            statistics.losing_positions += 1;
+            statistics.gross_loss -= profit;
+        }
+
+        statistics.net_profit += profit;
+        statistics.total_swaps += active_state.swaps.total;
+        statistics.total_fees += active_state.fees.get_total();
+
+        self.total_holding_microseconds += position.state.close_date.unix_microseconds
+            - active_state.open_data.open_date.unix_microseconds;
+
+        self.cumulative_profit += profit;
+        self.peak_profit = self.peak_profit.max(self.cumulative_profit);
+        statistics.max_drawdown = statistics
+            .max_drawdown
+            .max(self.peak_profit - self.cumulative_profit);
+    }
+
+    fn finish(self) -> MtTradingStatistics {
+        let mut statistics = self.statistics;
+
+        if statistics.total_positions > 0 {
+            statistics.win_rate =
+                statistics.winning_positions as f64 / statistics.total_positions as f64;
+            statistics.average_holding_microseconds =
+                self.total_holding_microseconds / statistics.total_positions as i64;
+        }
+
+        if statistics.winning_positions > 0 {
+            statistics.average_win = statistics.gross_profit / statistics.winning_positions as f64;
+        }
+
+        if statistics.losing_positions > 0 {
+            statistics.average_loss = statistics.gross_loss / statistics.losing_positions as f64;
+        }
+
+        if statistics.gross_loss > 0.0 {
+            statistics.profit_factor = Some(statistics.gross_profit / statistics.gross_loss);
+        }
+
+        statistics
+    }
+}
+
+fn sort_by_close_date<'a>(
+    positions: impl IntoIterator<Item = &'a MtPosition<MtPositionClosedState>>,
+) -> Vec<&'a MtPosition<MtPositionClosedState>> {
+    let mut positions: Vec<_> = positions.into_iter().collect();
+    positions.sort_by_key(|position| position.state.close_date.unix_microseconds);
+    positions
+}
+
+pub fn get_trading_statistics<'a>(
+    positions: impl IntoIterator<Item = &'a MtPosition<MtPositionClosedState>>,
+) -> MtTradingStatistics {
+    let mut accumulator = MtTradingStatisticsAccumulator::default();
+
+    for position in sort_by_close_date(positions) {
+        accumulator.add(position);
+    }
+
+    accumulator.finish()
+}
+
+pub fn get_trading_statistics_report<'a>(
+    positions: impl IntoIterator<Item = &'a MtPosition<MtPositionClosedState>>,
+    starting_balance: Option<f64>,
+    active_positions: Option<&[&MtPosition<MtPositionActiveState>]>,
+) -> MtTradingStatisticsReport {
+    let positions = sort_by_close_date(positions);
+
+    let mut totals = MtTradingStatisticsAccumulator::default();
+    let mut by_instrument: HashMap<String, MtTradingStatisticsAccumulator> = HashMap::new();
+    let mut by_close_reason: HashMap<MtPositionCloseReason, MtTradingStatisticsAccumulator> =
+        HashMap::new();
+
+    for position in positions.iter().copied() {
+        totals.add(position);
+
+        by_instrument
+            .entry(position.base_data.asset_pair.clone())
+            .or_default()
+            .add(position);
+
+        by_close_reason
+            .entry(position.state.close_reason.clone())
+            .or_default()
+            .add(position);
+    }
+
+    let equity_curve = starting_balance.map(|starting_balance| {
+        build_equity_curve(
+            starting_balance,
+            &positions,
+            active_positions.unwrap_or_default(),
+            DateTimeAsMicroseconds::now(),
+        )
+    });
+
+    MtTradingStatisticsReport {
+        totals: totals.finish(),
+        by_instrument: by_instrument
+            .into_iter()
+            .map(|(asset_pair, accumulator)| (asset_pair, accumulator.finish()))
+            .collect(),
+        by_close_reason: by_close_reason
+            .into_iter()
+            .map(|(close_reason, accumulator)| (close_reason, accumulator.finish()))
+            .collect(),
+        equity_curve,
+    }
+}
+
+pub fn get_equity_curve<'a>(
+    starting_balance: f64,
+    closed_positions: impl IntoIterator<Item = &'a MtPosition<MtPositionClosedState>>,
+    active_positions: &[&MtPosition<MtPositionActiveState>],
+    now: DateTimeAsMicroseconds,
+) -> Vec<MtEquityPoint> {
+    build_equity_curve(
+        starting_balance,
+        &sort_by_close_date(closed_positions),
+        active_positions,
+        now,
+    )
+}
+
+fn build_equity_curve(
+    starting_balance: f64,
+    sorted_closed_positions: &[&MtPosition<MtPositionClosedState>],
+    active_positions: &[&MtPosition<MtPositionActiveState>],
+    now: DateTimeAsMicroseconds,
+) -> Vec<MtEquityPoint> {
+    let mut balance = starting_balance;
+    let mut result = vec![];
+
+    for position in sorted_closed_positions {
+        balance += position.state.active_state.profit;
+        result.push(MtEquityPoint {
+            date: position.state.close_date,
+            balance,
+            equity: balance,
+        });
+    }
+
+    let unrealized_profit: f64 = active_positions
+        .iter()
+        .map(|position| position.state.profit)
+        .sum();
+
+    result.push(MtEquityPoint {
+        date: now,
+        balance,
+        equity: balance + unrealized_profit,
+    });
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_extensions::date_time::DateTimeAsMicroseconds;
+
+    use crate::{
+        convert_position_to_closed, get_equity_curve, get_trading_statistics,
+        get_trading_statistics_report, MtPosition, MtPositionActiveState, MtPositionCloseReason,
+        MtPositionClosedState, TestEntity,
+    };
+
+    fn create_positions() -> Vec<MtPosition<MtPositionClosedState>> {
+        [
+            ("EURUSD", -20.0, 3000, MtPositionCloseReason::StopLoss),
+            ("EURUSD", 50.0, 1000, MtPositionCloseReason::TakeProfit),
+            ("GBPUSD", -30.0, 2000, MtPositionCloseReason::StopLoss),
+            ("GBPUSD", 40.0, 4000, MtPositionCloseReason::ClientCommand),
+        ]
+        .into_iter()
+        .map(|(asset_pair, profit, close_date, close_reason)| {
+            let mut position: MtPosition<MtPositionActiveState> =
+                MtPosition::generate_test_entity();
+            position.base_data.asset_pair = asset_pair.to_string();
+            position.state.profit = profit;
+            position.state.swaps.total = -1.0;
+            position.state.fees.open_fee = 0.5;
+            position.state.open_data.open_date = DateTimeAsMicroseconds::new(close_date - 100);
+
+            let mut position =
+                convert_position_to_closed(position, close_reason, "close".to_string());
+            position.state.close_date = DateTimeAsMicroseconds::new(close_date);
+            position
+        })
+        .collect()
+    }
+
+    #[test]
+    fn test_trading_statistics() {
+        let positions = create_positions();
+        let statistics = get_trading_statistics(&positions);
+
+        assert_eq!(statistics.total_positions, 4);
+        assert_eq!(statistics.winning_positions, 2);
+        assert_eq!(statistics.losing_positions, 2);
+        assert_eq!(statistics.win_rate, 0.5);
+        assert_eq!(statistics.net_profit, 40.0);
+        assert_eq!(statistics.average_win, 45.0);
+        assert_eq!(statistics.average_loss, 25.0);
+        assert_eq!(statistics.profit_factor, Some(90.0 / 50.0));
+        assert_eq!(statistics.max_drawdown, 50.0);
+        assert_eq!(statistics.average_holding_microseconds, 100);
+        assert_eq!(statistics.total_swaps, -4.0);
+        assert_eq!(statistics.total_fees, 2.0);
+    }
+
+    #[test]
+    fn test_trading_statistics_report() {
+        let positions = create_positions();
+        let report = get_trading_statistics_report(&positions, None, None);
+
+        assert_eq!(report.totals.total_positions, 4);
+        assert_eq!(report.by_instrument["EURUSD"].net_profit, 30.0);
+        assert_eq!(report.by_instrument["GBPUSD"].max_drawdown, 30.0);
+        assert_eq!(
+            report.by_close_reason[&MtPositionCloseReason::StopLoss].losing_positions,
+            2
+        );
+        assert_eq!(
+            report.by_close_reason[&MtPositionCloseReason::StopLoss].profit_factor,
+            Some(0.0)
+        );
+        assert!(report.equity_curve.is_none());
+
+        let mut active: MtPosition<MtPositionActiveState> = MtPosition::generate_test_entity();
+        active.state.profit = 15.0;
+
+        let report = get_trading_statistics_report(&positions, Some(1000.0), Some(&[&active]));
+        let curve = report.equity_curve.unwrap();
+
+        assert_eq!(curve.len(), 5);
+        assert_eq!(curve[3].balance, 1040.0);
+        assert_eq!(curve[4].equity, 1055.0);
+
+        let report = get_trading_statistics_report(&positions, Some(1000.0), None);
+        assert_eq!(report.equity_curve.unwrap()[4].equity, 1040.0);
+
+        let empty = get_trading_statistics(&vec![]);
+        assert_eq!(empty.win_rate, 0.0);
+        assert_eq!(empty.profit_factor, None);
+    }
+
+    #[test]
+    fn test_equity_curve() {
+        let positions = create_positions();
+
+        let mut active: MtPosition<MtPositionActiveState> = MtPosition::generate_test_entity();
+        active.state.profit = 15.0;
+
+        let curve = get_equity_curve(
+            1000.0,
+            &positions,
+            &[&active],
+            DateTimeAsMicroseconds::new(5000),
+        );
+
+        let balances: Vec<f64> = curve.iter().map(|point| point.balance).collect();
+        assert_eq!(balances, vec![1050.0, 1020.0, 1000.0, 1040.0, 1040.0]);
+        assert_eq!(curve.last().unwrap().equity, 1055.0);
+    }
+}
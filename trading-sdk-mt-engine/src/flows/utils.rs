@@ -1,4 +1,7 @@
-use crate::{MtBidAsk, MtBidAskCache, MtPositionSide, MtPositionPendingStateType, MtEngineError};
+use crate::{
+    MtBidAsk, MtBidAskCache, MtEngineError, MtPendingOrderKind, MtPositionPendingStateType,
+    MtPositionSide, MtValidationViolation,
+};
 
 pub fn get_open_price(bid_ask: &MtBidAsk, side: &MtPositionSide) -> f64 {
     match side {
@@ -14,6 +17,19 @@ pub fn get_close_price(bid_ask: &MtBidAsk, side: &MtPositionSide) -> f64 {
     }
 }
 
+// Market-if-touched orders trigger on the close side of the quote, so they are validated
+// against the same side. Every other pending order is placed relative to the open side.
+pub fn get_pending_order_reference_price(
+    bid_ask: &MtBidAsk,
+    side: &MtPositionSide,
+    order_kind: &MtPendingOrderKind,
+) -> f64 {
+    match order_kind {
+        MtPendingOrderKind::MarketIfTouched => get_close_price(bid_ask, side),
+        _ => get_open_price(bid_ask, side),
+    }
+}
+
 pub fn get_pending_position_type(
     current_price: f64,
     desire_price: f64,
@@ -37,6 +53,72 @@ pub fn get_pending_position_type(
     }
 }
 
+pub fn get_pending_order_type(
+    current_price: f64,
+    desire_price: f64,
+    side: &MtPositionSide,
+    order_kind: &MtPendingOrderKind,
+) -> Result<MtPositionPendingStateType, MtValidationViolation> {
+    let is_above_market = desire_price > current_price;
+
+    match order_kind {
+        MtPendingOrderKind::Regular => {
+            Ok(get_pending_position_type(current_price, desire_price, side))
+        }
+        MtPendingOrderKind::StopLimit { limit_price } => {
+            let (is_trigger_valid, is_limit_valid, position_type) = match side {
+                MtPositionSide::Buy => (
+                    is_above_market,
+                    *limit_price >= desire_price,
+                    MtPositionPendingStateType::BuyStopLimit,
+                ),
+                MtPositionSide::Sell => (
+                    !is_above_market,
+                    *limit_price <= desire_price,
+                    MtPositionPendingStateType::SellStopLimit,
+                ),
+            };
+
+            if !is_trigger_valid {
+                return Err(MtValidationViolation::PendingTriggerPriceInvalid {
+                    price: desire_price,
+                    current_price,
+                });
+            }
+
+            if !is_limit_valid {
+                return Err(MtValidationViolation::PendingLimitPriceInvalid {
+                    limit_price: *limit_price,
+                    trigger_price: desire_price,
+                });
+            }
+
+            Ok(position_type)
+        }
+        MtPendingOrderKind::MarketIfTouched => {
+            let (is_trigger_valid, position_type) = match side {
+                MtPositionSide::Buy => (
+                    !is_above_market,
+                    MtPositionPendingStateType::BuyMarketIfTouched,
+                ),
+                MtPositionSide::Sell => (
+                    is_above_market,
+                    MtPositionPendingStateType::SellMarketIfTouched,
+                ),
+            };
+
+            if !is_trigger_valid {
+                return Err(MtValidationViolation::PendingTriggerPriceInvalid {
+                    price: desire_price,
+                    current_price,
+                });
+            }
+
+            Ok(position_type)
+        }
+    }
+}
+
 pub fn get_any_price_by_tickers(
     prices_cache: &MtBidAskCache,
     ticker1: &str,
@@ -65,7 +147,10 @@ pub fn get_base_collateral_open_price(
 
     match get_any_price_by_tickers(prices_cache, collateral, base) {
         Some(src) => Ok((get_open_price(&src, side), Some(src))),
-        None => Err(MtEngineError::NoLiquidity),
+        None => Err(MtEngineError::NoConversionLiquidity {
+            asset: base.to_string(),
+            collateral: collateral.to_string(),
+        }),
     }
 }
 
@@ -81,6 +166,9 @@ pub fn get_quote_collateral_close_price(
 
     match get_any_price_by_tickers(prices_cache, collateral, quote) {
         Some(src) => Ok((get_close_price(&src, side), Some(src))),
-        None => Err(MtEngineError::NoLiquidity),
+        None => Err(MtEngineError::NoConversionLiquidity {
+            asset: quote.to_string(),
+            collateral: collateral.to_string(),
+        }),
     }
 }
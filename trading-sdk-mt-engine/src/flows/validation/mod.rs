@@ -0,0 +1,7 @@
+mod price_precision;
+mod validate_position_open;
+mod validate_protection;
+
+pub use price_precision::*;
+pub use validate_position_open::*;
+pub use validate_protection::*;
@@ -0,0 +1,123 @@
+use crate::{MtBidAsk, MtInstrument, MtOffTickPolicy};
+
+const MAX_PRICE_DIGITS: u32 = 10;
+
+pub fn get_price_digits(instrument: &MtInstrument) -> Option<u32> {
+    if instrument.digits.is_some() {
+        return instrument.digits;
+    }
+
+    let tick_size = instrument.tick_size.filter(|tick_size| *tick_size > 0.0)?;
+
+    (0..=MAX_PRICE_DIGITS).find(|digits| {
+        let scaled = tick_size * 10f64.powi(*digits as i32);
+        (scaled - scaled.round()).abs() < 1e-9 * scaled.max(1.0)
+    })
+}
+
+pub fn round_price_to_digits(price: f64, digits: u32) -> f64 {
+    let factor = 10f64.powi(digits as i32);
+    (price * factor).round() / factor
+}
+
+pub fn normalize_price(instrument: &MtInstrument, price: f64) -> f64 {
+    let mut price = price;
+
+    if let Some(tick_size) = instrument.tick_size.filter(|tick_size| *tick_size > 0.0) {
+        price = (price / tick_size).round() * tick_size;
+    }
+
+    match get_price_digits(instrument) {
+        Some(digits) => round_price_to_digits(price, digits),
+        None => price,
+    }
+}
+
+pub fn normalize_optional_price(instrument: &MtInstrument, price: Option<f64>) -> Option<f64> {
+    price.map(|price| normalize_price(instrument, price))
+}
+
+pub fn normalize_bid_ask(instrument: &MtInstrument, bid_ask: MtBidAsk) -> MtBidAsk {
+    MtBidAsk {
+        bid: normalize_price(instrument, bid_ask.bid),
+        ask: normalize_price(instrument, bid_ask.ask),
+        ..bid_ask
+    }
+}
+
+pub fn format_price(instrument: &MtInstrument, price: f64) -> String {
+    match get_price_digits(instrument) {
+        Some(digits) => format!("{:.*}", digits as usize, price),
+        None => price.to_string(),
+    }
+}
+
+pub fn is_off_tick_rounding_allowed(instrument: &MtInstrument) -> bool {
+    matches!(instrument.off_tick_policy, MtOffTickPolicy::Round)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        format_price, get_price_digits, normalize_bid_ask, normalize_price, MtBidAsk, MtInstrument,
+        TestEntity,
+    };
+
+    #[test]
+    fn test_price_digits() {
+        let mut instrument = MtInstrument::generate_test_entity();
+        assert_eq!(get_price_digits(&instrument), None);
+
+        instrument.tick_size = Some(0.25);
+        assert_eq!(get_price_digits(&instrument), Some(2));
+
+        instrument.tick_size = Some(0.00001);
+        assert_eq!(get_price_digits(&instrument), Some(5));
+
+        instrument.digits = Some(3);
+        assert_eq!(get_price_digits(&instrument), Some(3));
+    }
+
+    #[test]
+    fn test_normalize_price() {
+        let mut instrument = MtInstrument::generate_test_entity();
+        assert_eq!(normalize_price(&instrument, 1.0688001), 1.0688001);
+
+        instrument.tick_size = Some(0.0001);
+        assert_eq!(normalize_price(&instrument, 1.0688001), 1.0688);
+        assert_eq!(normalize_price(&instrument, 1.06876), 1.0688);
+
+        instrument.tick_size = Some(0.25);
+        assert_eq!(normalize_price(&instrument, 100.13), 100.25);
+        assert_eq!(normalize_price(&instrument, 100.12), 100.0);
+
+        instrument.tick_size = None;
+        instrument.digits = Some(2);
+        assert_eq!(normalize_price(&instrument, 1.23456), 1.23);
+    }
+
+    #[test]
+    fn test_normalize_bid_ask() {
+        let mut instrument = MtInstrument::generate_test_entity();
+        instrument.tick_size = Some(0.001);
+
+        let mut bid_ask = MtBidAsk::generate_test_entity();
+        bid_ask.bid = 25.0004999;
+        bid_ask.ask = 25.0015001;
+
+        let bid_ask = normalize_bid_ask(&instrument, bid_ask);
+
+        assert_eq!(bid_ask.bid, 25.0);
+        assert_eq!(bid_ask.ask, 25.002);
+    }
+
+    #[test]
+    fn test_format_price() {
+        let mut instrument = MtInstrument::generate_test_entity();
+        assert_eq!(format_price(&instrument, 1.5), "1.5");
+
+        instrument.digits = Some(5);
+        assert_eq!(format_price(&instrument, 1.5), "1.50000");
+        assert_eq!(format_price(&instrument, 1.0688000000000002), "1.06880");
+    }
+}
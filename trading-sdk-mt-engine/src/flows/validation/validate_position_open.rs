@@ -0,0 +1,356 @@
+use rust_extensions::date_time::DateTimeAsMicroseconds;
+
+use crate::{
+    get_open_price, is_off_tick_rounding_allowed, MtBidAsk, MtEngineError, MtInstrument,
+    MtPositionSide, MtValidationViolation,
+};
+
+pub fn validate_instrument_asset_pair(
+    instrument: &MtInstrument,
+    bid_ask: &MtBidAsk,
+    base: &str,
+    quote: &str,
+) -> Result<(), MtValidationViolation> {
+    validate_instrument_status(instrument)?;
+    validate_asset_pair(instrument, bid_ask, base, quote)
+}
+
+pub fn validate_asset_pair(
+    instrument: &MtInstrument,
+    bid_ask: &MtBidAsk,
+    base: &str,
+    quote: &str,
+) -> Result<(), MtValidationViolation> {
+    for (expected_base, expected_quote) in [
+        (&instrument.base, &instrument.quote),
+        (&bid_ask.base, &bid_ask.quote),
+    ] {
+        if expected_base != base || expected_quote != quote {
+            return Err(MtValidationViolation::AssetPairMismatch {
+                expected_base: expected_base.to_string(),
+                expected_quote: expected_quote.to_string(),
+                base: base.to_string(),
+                quote: quote.to_string(),
+            });
+        }
+    }
+
+    Ok(())
+}
+
+pub fn validate_instrument_status(instrument: &MtInstrument) -> Result<(), MtValidationViolation> {
+    if !instrument.status.is_open_allowed() {
+        return Err(MtValidationViolation::InstrumentStatusRestricted {
+            status: instrument.status.clone(),
+        });
+    }
+
+    Ok(())
+}
+
+pub fn validate_invest_amount(
+    instrument: &MtInstrument,
+    invest_amount: f64,
+    leverage: f64,
+) -> Result<(), MtValidationViolation> {
+    if let Some(min) = instrument.min_invest_amount {
+        if invest_amount < min {
+            return Err(MtValidationViolation::InvestAmountTooLow {
+                min,
+                actual: invest_amount,
+            });
+        }
+    }
+
+    if let Some(max) = instrument.max_invest_amount {
+        if invest_amount > max {
+            return Err(MtValidationViolation::InvestAmountTooHigh {
+                max,
+                actual: invest_amount,
+            });
+        }
+    }
+
+    if let Some(allowed) = &instrument.allowed_leverages {
+        if !allowed.contains(&leverage) {
+            return Err(MtValidationViolation::LeverageNotAllowed {
+                allowed: allowed.clone(),
+                actual: leverage,
+            });
+        }
+    }
+
+    Ok(())
+}
+
+pub fn validate_price_on_tick(
+    instrument: &MtInstrument,
+    price: Option<f64>,
+) -> Result<(), MtValidationViolation> {
+    let (Some(price), Some(tick_size)) = (price, instrument.tick_size) else {
+        return Ok(());
+    };
+
+    if is_off_tick_rounding_allowed(instrument) {
+        return Ok(());
+    }
+
+    if !is_price_on_tick(price, tick_size) {
+        return Err(MtValidationViolation::PriceNotOnTick { price, tick_size });
+    }
+
+    Ok(())
+}
+
+pub fn validate_price_age(
+    instrument: &MtInstrument,
+    bid_ask: &MtBidAsk,
+    now: DateTimeAsMicroseconds,
+) -> Result<(), MtEngineError> {
+    let Some(max_age) = instrument.max_price_age_microseconds else {
+        return Ok(());
+    };
+
+    let age_microseconds = now.unix_microseconds - bid_ask.date.unix_microseconds;
+
+    if age_microseconds > max_age {
+        return Err(MtEngineError::StalePrice {
+            asset_pair: bid_ask.asset_pair.clone(),
+            age_microseconds,
+        });
+    }
+
+    Ok(())
+}
+
+pub fn validate_price_deviation(
+    instrument: &MtInstrument,
+    bid_ask: &MtBidAsk,
+    side: &MtPositionSide,
+    expected_price: Option<f64>,
+    max_deviation: Option<f64>,
+) -> Result<(), MtEngineError> {
+    validate_execution_price_deviation(
+        instrument,
+        &bid_ask.asset_pair,
+        side,
+        get_open_price(bid_ask, side),
+        expected_price,
+        max_deviation,
+    )
+}
+
+pub fn validate_execution_price_deviation(
+    instrument: &MtInstrument,
+    asset_pair: &str,
+    side: &MtPositionSide,
+    actual_price: f64,
+    expected_price: Option<f64>,
+    max_deviation: Option<f64>,
+) -> Result<(), MtEngineError> {
+    let Some(expected_price) = expected_price else {
+        return Ok(());
+    };
+
+    let max_deviation = max_deviation.unwrap_or(0.0);
+
+    let adverse_deviation = match side {
+        MtPositionSide::Buy => actual_price - expected_price,
+        MtPositionSide::Sell => expected_price - actual_price,
+    };
+
+    let is_allowed = if adverse_deviation < 0.0 && instrument.allow_positive_slippage {
+        true
+    } else {
+        adverse_deviation.abs() <= max_deviation
+    };
+
+    if !is_allowed {
+        return Err(MtEngineError::Requote {
+            asset_pair: asset_pair.to_string(),
+            expected_price,
+            actual_price,
+        });
+    }
+
+    Ok(())
+}
+
+pub fn is_price_on_tick(price: f64, tick_size: f64) -> bool {
+    if tick_size <= 0.0 {
+        return true;
+    }
+
+    let ticks = price / tick_size;
+    (ticks - ticks.round()).abs() < 1e-6
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_extensions::date_time::DateTimeAsMicroseconds;
+
+    use crate::{
+        is_price_on_tick, validate_instrument_asset_pair, validate_invest_amount,
+        validate_price_age, validate_price_deviation, validate_price_on_tick, MtBidAsk,
+        MtEngineError, MtInstrument, MtInstrumentStatus, MtPositionSide, MtValidationViolation,
+        TestEntity,
+    };
+
+    #[test]
+    fn test_trading_disabled() {
+        let mut instrument = MtInstrument::generate_test_entity();
+        instrument.status = MtInstrumentStatus::CloseOnly;
+
+        let result = validate_instrument_asset_pair(
+            &instrument,
+            &MtBidAsk::generate_test_entity(),
+            "base",
+            "quote",
+        );
+
+        assert!(matches!(
+            result,
+            Err(MtValidationViolation::InstrumentStatusRestricted {
+                status: MtInstrumentStatus::CloseOnly
+            })
+        ));
+    }
+
+    #[test]
+    fn test_asset_pair_mismatch() {
+        let instrument = MtInstrument::generate_test_entity();
+        let bid_ask = MtBidAsk::generate_test_entity();
+
+        assert!(validate_instrument_asset_pair(&instrument, &bid_ask, "base", "quote").is_ok());
+
+        let Err(MtValidationViolation::AssetPairMismatch {
+            expected_base,
+            base,
+            ..
+        }) = validate_instrument_asset_pair(&instrument, &bid_ask, "quote", "base")
+        else {
+            panic!("Expected asset pair mismatch");
+        };
+
+        assert_eq!(expected_base, "base");
+        assert_eq!(base, "quote");
+    }
+
+    #[test]
+    fn test_invest_amount_limits() {
+        let mut instrument = MtInstrument::generate_test_entity();
+        instrument.min_invest_amount = Some(10.0);
+        instrument.max_invest_amount = Some(100.0);
+        instrument.allowed_leverages = Some(vec![10.0, 50.0]);
+
+        assert!(validate_invest_amount(&instrument, 50.0, 10.0).is_ok());
+        assert!(matches!(
+            validate_invest_amount(&instrument, 5.0, 10.0),
+            Err(MtValidationViolation::InvestAmountTooLow { .. })
+        ));
+        assert!(matches!(
+            validate_invest_amount(&instrument, 500.0, 10.0),
+            Err(MtValidationViolation::InvestAmountTooHigh { .. })
+        ));
+        assert!(matches!(
+            validate_invest_amount(&instrument, 50.0, 20.0),
+            Err(MtValidationViolation::LeverageNotAllowed { .. })
+        ));
+    }
+
+    #[test]
+    fn test_price_on_tick() {
+        let mut instrument = MtInstrument::generate_test_entity();
+        instrument.tick_size = Some(0.00001);
+
+        assert!(is_price_on_tick(1.06881, 0.00001));
+        assert!(is_price_on_tick(25.5, 0.25));
+        assert!(!is_price_on_tick(25.6, 0.25));
+
+        assert!(validate_price_on_tick(&instrument, None).is_ok());
+        assert!(validate_price_on_tick(&instrument, Some(1.06881)).is_ok());
+        assert!(matches!(
+            validate_price_on_tick(&instrument, Some(1.068815)),
+            Err(MtValidationViolation::PriceNotOnTick { .. })
+        ));
+    }
+
+    #[test]
+    fn test_stale_price() {
+        let mut instrument = MtInstrument::generate_test_entity();
+        instrument.max_price_age_microseconds = Some(1_000_000);
+
+        let mut bid_ask = MtBidAsk::generate_test_entity();
+        bid_ask.date = DateTimeAsMicroseconds::new(10_000_000);
+
+        assert!(validate_price_age(
+            &instrument,
+            &bid_ask,
+            DateTimeAsMicroseconds::new(10_500_000)
+        )
+        .is_ok());
+
+        let result = validate_price_age(
+            &instrument,
+            &bid_ask,
+            DateTimeAsMicroseconds::new(12_000_000),
+        );
+
+        let Err(MtEngineError::StalePrice {
+            asset_pair,
+            age_microseconds,
+        }) = result
+        else {
+            panic!("Expected stale price");
+        };
+
+        assert_eq!(asset_pair, "BASEQUOTE");
+        assert_eq!(age_microseconds, 2_000_000);
+    }
+
+    #[test]
+    fn test_price_deviation() {
+        let mut instrument = MtInstrument::generate_test_entity();
+        let mut bid_ask = MtBidAsk::generate_test_entity();
+        bid_ask.bid = 24.5;
+        bid_ask.ask = 25.0;
+
+        let buy = MtPositionSide::Buy;
+        let sell = MtPositionSide::Sell;
+
+        assert!(validate_price_deviation(&instrument, &bid_ask, &buy, None, None).is_ok());
+        assert!(validate_price_deviation(&instrument, &bid_ask, &buy, Some(25.0), None).is_ok());
+        assert!(
+            validate_price_deviation(&instrument, &bid_ask, &buy, Some(24.75), Some(0.25)).is_ok()
+        );
+
+        let result = validate_price_deviation(&instrument, &bid_ask, &buy, Some(24.5), Some(0.25));
+
+        let Err(MtEngineError::Requote {
+            expected_price,
+            actual_price,
+            ..
+        }) = result
+        else {
+            panic!("Expected requote");
+        };
+
+        assert_eq!(expected_price, 24.5);
+        assert_eq!(actual_price, 25.0);
+
+        assert!(
+            validate_price_deviation(&instrument, &bid_ask, &sell, Some(25.0), Some(0.25)).is_err()
+        );
+        assert!(validate_price_deviation(&instrument, &bid_ask, &buy, Some(26.0), None).is_ok());
+        assert!(validate_price_deviation(&instrument, &bid_ask, &sell, Some(24.0), None).is_ok());
+
+        instrument.allow_positive_slippage = false;
+
+        assert!(
+            validate_price_deviation(&instrument, &bid_ask, &buy, Some(26.0), Some(0.5)).is_err()
+        );
+        assert!(
+            validate_price_deviation(&instrument, &bid_ask, &buy, Some(25.5), Some(0.5)).is_ok()
+        );
+    }
+}
@@ -0,0 +1,198 @@
+use crate::{
+    validate_price_on_tick, MtInstrument, MtPendingBracket, MtPositionProtection, MtPositionSide,
+    MtValidationViolation,
+};
+
+pub fn validate_protection_levels(
+    instrument: &MtInstrument,
+    protection: &MtPositionProtection,
+    side: &MtPositionSide,
+    reference_price: f64,
+    current_profit: f64,
+) -> Result<(), MtValidationViolation> {
+    validate_price_on_tick(instrument, protection.sl_price)?;
+    validate_price_on_tick(instrument, protection.tp_price)?;
+
+    if let Some(sl_price) = protection.sl_price {
+        let is_valid = match side {
+            MtPositionSide::Buy => sl_price < reference_price,
+            MtPositionSide::Sell => sl_price > reference_price,
+        };
+
+        if !is_valid {
+            return Err(MtValidationViolation::StopLossPriceInvalid {
+                price: sl_price,
+                reference_price,
+            });
+        }
+    }
+
+    if let Some(tp_price) = protection.tp_price {
+        let is_valid = match side {
+            MtPositionSide::Buy => tp_price > reference_price,
+            MtPositionSide::Sell => tp_price < reference_price,
+        };
+
+        if !is_valid {
+            return Err(MtValidationViolation::TakeProfitPriceInvalid {
+                price: tp_price,
+                reference_price,
+            });
+        }
+    }
+
+    if let Some(sl_profit) = protection.sl_profit {
+        if sl_profit >= current_profit {
+            return Err(MtValidationViolation::StopLossProfitInvalid {
+                profit: sl_profit,
+                current_profit,
+            });
+        }
+    }
+
+    if let Some(tp_profit) = protection.tp_profit {
+        if tp_profit <= current_profit {
+            return Err(MtValidationViolation::TakeProfitProfitInvalid {
+                profit: tp_profit,
+                current_profit,
+            });
+        }
+    }
+
+    Ok(())
+}
+
+pub fn validate_bracket_protection(
+    bracket: &MtPendingBracket,
+    protection: &MtPositionProtection,
+) -> Result<(), MtValidationViolation> {
+    let is_sl_conflict = bracket.sl_distance.is_some()
+        && (protection.sl_price.is_some() || protection.sl_profit.is_some());
+    let is_tp_conflict = bracket.tp_distance.is_some()
+        && (protection.tp_price.is_some() || protection.tp_profit.is_some());
+
+    if is_sl_conflict || is_tp_conflict {
+        return Err(MtValidationViolation::BracketLevelConflict);
+    }
+
+    Ok(())
+}
+
+pub fn validate_pending_bracket(
+    instrument: &MtInstrument,
+    bracket: &MtPendingBracket,
+) -> Result<(), MtValidationViolation> {
+    for distance in [bracket.sl_distance, bracket.tp_distance]
+        .into_iter()
+        .flatten()
+    {
+        if distance <= 0.0 {
+            return Err(MtValidationViolation::BracketDistanceInvalid { distance });
+        }
+
+        validate_price_on_tick(instrument, Some(distance))?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        validate_bracket_protection, validate_protection_levels, MtInstrument, MtPendingBracket,
+        MtPositionProtection, MtPositionSide, MtValidationViolation, TestEntity,
+    };
+
+    fn protection(sl_price: Option<f64>, tp_price: Option<f64>) -> MtPositionProtection {
+        MtPositionProtection {
+            tp_profit: None,
+            tp_price,
+            sl_profit: None,
+            sl_price,
+        }
+    }
+
+    #[test]
+    fn test_buy_levels() {
+        let instrument = MtInstrument::generate_test_entity();
+        let side = MtPositionSide::Buy;
+
+        let result = validate_protection_levels(
+            &instrument,
+            &protection(Some(1.0), Some(1.2)),
+            &side,
+            1.1,
+            0.0,
+        );
+        assert!(result.is_ok());
+
+        let result =
+            validate_protection_levels(&instrument, &protection(Some(1.1), None), &side, 1.1, 0.0);
+        assert!(matches!(
+            result,
+            Err(MtValidationViolation::StopLossPriceInvalid { .. })
+        ));
+
+        let result =
+            validate_protection_levels(&instrument, &protection(None, Some(1.0)), &side, 1.1, 0.0);
+        assert!(matches!(
+            result,
+            Err(MtValidationViolation::TakeProfitPriceInvalid { .. })
+        ));
+    }
+
+    #[test]
+    fn test_sell_levels() {
+        let instrument = MtInstrument::generate_test_entity();
+        let side = MtPositionSide::Sell;
+
+        let result = validate_protection_levels(
+            &instrument,
+            &protection(Some(1.2), Some(1.0)),
+            &side,
+            1.1,
+            0.0,
+        );
+        assert!(result.is_ok());
+
+        let result =
+            validate_protection_levels(&instrument, &protection(Some(1.0), None), &side, 1.1, 0.0);
+        assert!(matches!(
+            result,
+            Err(MtValidationViolation::StopLossPriceInvalid { .. })
+        ));
+    }
+
+    #[test]
+    fn test_profit_levels() {
+        let instrument = MtInstrument::generate_test_entity();
+        let mut levels = protection(None, None);
+        levels.sl_profit = Some(-50.0);
+        levels.tp_profit = Some(50.0);
+
+        let result =
+            validate_protection_levels(&instrument, &levels, &MtPositionSide::Buy, 1.1, -10.0);
+        assert!(result.is_ok());
+
+        let result =
+            validate_protection_levels(&instrument, &levels, &MtPositionSide::Buy, 1.1, -60.0);
+        assert!(matches!(
+            result,
+            Err(MtValidationViolation::StopLossProfitInvalid { .. })
+        ));
+    }
+
+    #[test]
+    fn test_bracket_level_conflict() {
+        let bracket = MtPendingBracket {
+            sl_distance: Some(0.1),
+            tp_distance: None,
+        };
+
+        assert!(validate_bracket_protection(&bracket, &protection(None, Some(1.2))).is_ok());
+        assert!(matches!(
+            validate_bracket_protection(&bracket, &protection(Some(1.0), None)),
+            Err(MtValidationViolation::BracketLevelConflict)
+        ));
+    }
+}
@@ -1,3 +1,7 @@
+mod mt_fake_liquidity_provider;
+
+pub use mt_fake_liquidity_provider::*;
+
 pub trait TestEntity {
     fn generate_test_entity() -> Self;
 }
@@ -15,24 +19,24 @@ mod test {
         position.base_data.margin_call_percent = Some(50.0);
         position.base_data.topping_up_percent = Some(50.0);
 
-        let mkh = is_margin_call_hit(&position);
-        let mka = calculate_position_topping_up(&position.base_data);
+        let mkh = is_margin_call_hit(&position).unwrap();
+        let mka = calculate_position_topping_up(&position.base_data).unwrap();
     
-        apply_position_topping_up(500.0, &mut position);
+        apply_position_topping_up(500.0, &mut position).unwrap();
 
         println!("1) {} - {:?}", mkh, mka);
 
 
-        let mkh = is_margin_call_hit(&position);
-        let mka = calculate_position_topping_up(&position.base_data);
-        let crt = can_return_topping_up_funds(&position);
+        let mkh = is_margin_call_hit(&position).unwrap();
+        let mka = calculate_position_topping_up(&position.base_data).unwrap();
+        let crt = can_return_topping_up_funds(&position).unwrap();
         println!("2) {} - {:?} - {}", mkh, mka, crt);
 
         position.state.profit = -499.0;
         
-        let mkh = is_margin_call_hit(&position);
-        let mka = calculate_position_topping_up(&position.base_data);
-        let crt = can_return_topping_up_funds(&position);
+        let mkh = is_margin_call_hit(&position).unwrap();
+        let mka = calculate_position_topping_up(&position.base_data).unwrap();
+        let crt = can_return_topping_up_funds(&position).unwrap();
         println!("3) {} - {:?} - {}", mkh, mka, crt);
     }
 }
@@ -0,0 +1,69 @@
+use std::sync::Mutex;
+
+use rust_extensions::date_time::DateTimeAsMicroseconds;
+
+use crate::{
+    MtEngineError, MtLiquidityProvider, MtLiquidityProviderFill, MtLiquidityProviderOrder,
+    MtPositionSide,
+};
+
+pub struct MtFakeLiquidityProvider {
+    pub slippage: f64,
+    pub is_rejecting: bool,
+    orders: Mutex<Vec<MtLiquidityProviderOrder>>,
+}
+
+impl MtFakeLiquidityProvider {
+    pub fn new(slippage: f64) -> Self {
+        Self {
+            slippage,
+            is_rejecting: false,
+            orders: Mutex::new(vec![]),
+        }
+    }
+
+    pub fn get_orders(&self) -> Vec<MtLiquidityProviderOrder> {
+        self.orders.lock().unwrap().clone()
+    }
+}
+
+impl MtLiquidityProvider for MtFakeLiquidityProvider {
+    async fn execute_order(
+        &self,
+        order: MtLiquidityProviderOrder,
+    ) -> Result<MtLiquidityProviderFill, MtEngineError> {
+        if self.is_rejecting {
+            return Err(MtEngineError::LiquidityProviderRejected {
+                asset_pair: order.asset_pair,
+                reason: "rejected by fake liquidity provider".to_string(),
+            });
+        }
+
+        let price = match order.side {
+            MtPositionSide::Buy => order.requested_price + self.slippage,
+            MtPositionSide::Sell => order.requested_price - self.slippage,
+        };
+
+        if let Some(price_limit) = &order.price_limit {
+            let adverse_deviation = match order.side {
+                MtPositionSide::Buy => price - price_limit.expected_price,
+                MtPositionSide::Sell => price_limit.expected_price - price,
+            };
+
+            if adverse_deviation > price_limit.max_deviation {
+                return Err(MtEngineError::Requote {
+                    asset_pair: order.asset_pair,
+                    expected_price: price_limit.expected_price,
+                    actual_price: price,
+                });
+            }
+        }
+
+        self.orders.lock().unwrap().push(order);
+
+        Ok(MtLiquidityProviderFill {
+            price,
+            date: DateTimeAsMicroseconds::now(),
+        })
+    }
+}